@@ -0,0 +1,488 @@
+fn main() {
+    #[cfg(feature = "devtools-protocol")]
+    devtools_protocol::generate();
+}
+
+/// Generate typed DevTools protocol domains from `browser_protocol.json` and `js_protocol.json`.
+///
+/// The schema files are read from the directory in `CEF_DEVTOOLS_PROTOCOL_PATH`. They can be
+/// found in `third_party/blink/public/devtools_protocol` and `v8/include` of the Chromium source
+/// matching the cef version, or downloaded from the `devtools-protocol` repository.
+#[cfg(feature = "devtools-protocol")]
+mod devtools_protocol {
+    use serde_json::Value;
+    use std::{
+        collections::{HashMap, HashSet},
+        fmt::Write,
+        path::PathBuf,
+    };
+
+    const PROTOCOL_PATH: &str = "CEF_DEVTOOLS_PROTOCOL_PATH";
+    const SCHEMAS: &[&str] = &["browser_protocol.json", "js_protocol.json"];
+
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+        "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+        "return", "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe",
+        "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+
+    pub fn generate() {
+        println!("cargo:rerun-if-env-changed={PROTOCOL_PATH}");
+        let out = PathBuf::from(std::env::var("OUT_DIR").unwrap()).join("devtools_protocol.rs");
+        let Ok(dir) = std::env::var(PROTOCOL_PATH).map(PathBuf::from) else {
+            // Keep `--all-features` builds working, `cef::protocol` just has no domains.
+            println!(
+                "cargo:warning=`devtools-protocol` feature requires {PROTOCOL_PATH} to point to the directory of {SCHEMAS:?}, no domains are generated"
+            );
+            std::fs::write(out, Generator::new(&[]).generate()).unwrap();
+            return;
+        };
+
+        let mut domains = Vec::new();
+        for schema in SCHEMAS {
+            let path = dir.join(schema);
+            println!("cargo:rerun-if-changed={}", path.display());
+            let content = std::fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("cannot read {}: {e}", path.display()));
+            let mut protocol: Value = serde_json::from_str(&content)
+                .unwrap_or_else(|e| panic!("cannot parse {}: {e}", path.display()));
+            if let Some(Value::Array(list)) = protocol.get_mut("domains").map(Value::take) {
+                domains.extend(list);
+            }
+        }
+
+        let code = Generator::new(&domains).generate();
+        std::fs::write(out, code).unwrap();
+    }
+
+    fn str_of<'a>(value: &'a Value, key: &str) -> &'a str {
+        value.get(key).and_then(Value::as_str).unwrap_or_default()
+    }
+
+    fn list_of<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+        value
+            .get(key)
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    fn split_words(name: &str) -> Vec<String> {
+        let chars: Vec<char> = name.chars().collect();
+        let mut words = Vec::new();
+        let mut word = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            if !c.is_ascii_alphanumeric() {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+                continue;
+            }
+            if c.is_ascii_uppercase() && !word.is_empty() {
+                let prev = chars[i - 1];
+                let next_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+                if prev.is_ascii_lowercase()
+                    || prev.is_ascii_digit()
+                    || (prev.is_ascii_uppercase() && next_lower)
+                {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            word.push(c);
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+        words
+    }
+
+    fn snake_case(name: &str) -> String {
+        let name = split_words(name)
+            .iter()
+            .map(|w| w.to_ascii_lowercase())
+            .collect::<Vec<_>>()
+            .join("_");
+        match name.as_str() {
+            "self" | "super" | "crate" => format!("{name}_"),
+            n if KEYWORDS.contains(&n) => format!("r#{name}"),
+            _ => name,
+        }
+    }
+
+    fn pascal_case(name: &str) -> String {
+        let name: String = split_words(name)
+            .iter()
+            .map(|w| {
+                let mut chars = w.chars();
+                let first = chars.next().unwrap().to_ascii_uppercase();
+                std::iter::once(first).chain(chars).collect::<String>()
+            })
+            .collect();
+        match name.as_str() {
+            "" => "Empty".to_string(),
+            "Self" => "Self_".to_string(),
+            n if n.starts_with(|c: char| c.is_ascii_digit()) => format!("V{name}"),
+            _ => name,
+        }
+    }
+
+    fn doc(out: &mut String, indent: &str, item: &Value) {
+        let description = str_of(item, "description");
+        if !description.is_empty() {
+            writeln!(out, "{indent}#[doc = {description:?}]").unwrap();
+        }
+    }
+
+    struct Generator<'a> {
+        domains: &'a [Value],
+        /// Type definitions by `Domain.Type`.
+        types: HashMap<String, &'a Value>,
+        defaultable: HashMap<String, bool>,
+    }
+
+    impl<'a> Generator<'a> {
+        fn new(domains: &'a [Value]) -> Self {
+            let mut types = HashMap::new();
+            for domain in domains {
+                let name = str_of(domain, "domain");
+                for ty in list_of(domain, "types") {
+                    types.insert(format!("{name}.{}", str_of(ty, "id")), ty);
+                }
+            }
+            Self {
+                domains,
+                types,
+                defaultable: HashMap::new(),
+            }
+        }
+
+        fn qualify(domain: &str, reference: &str) -> String {
+            if reference.contains('.') {
+                reference.to_string()
+            } else {
+                format!("{domain}.{reference}")
+            }
+        }
+
+        fn is_struct(ty: &Value) -> bool {
+            str_of(ty, "type") == "object" && ty.get("properties").is_some()
+        }
+
+        /// Whether `from` reaches `to` through fields that are stored inline.
+        fn reaches(&self, from: &str, to: &str, visited: &mut HashSet<String>) -> bool {
+            if from == to {
+                return true;
+            }
+            if !visited.insert(from.to_string()) {
+                return false;
+            }
+            let Some(ty) = self.types.get(from) else {
+                return false;
+            };
+            let domain = from.split('.').next().unwrap();
+            list_of(ty, "properties")
+                .iter()
+                .any(|p| match p.get("$ref") {
+                    Some(Value::String(r)) => self.reaches(&Self::qualify(domain, r), to, visited),
+                    _ => false,
+                })
+        }
+
+        fn is_defaultable(&mut self, domain: &str, item: &Value) -> bool {
+            if str_of(item, "type") == "array" {
+                return true;
+            }
+            if item.get("enum").is_some() {
+                return false;
+            }
+            let Some(reference) = item.get("$ref").and_then(Value::as_str) else {
+                return true;
+            };
+            let key = Self::qualify(domain, reference);
+            if let Some(&known) = self.defaultable.get(&key) {
+                return known;
+            }
+            self.defaultable.insert(key.clone(), false);
+            let Some(&ty) = self.types.get(&key) else {
+                return false;
+            };
+            let owner = key.split('.').next().unwrap().to_string();
+            let result = if Self::is_struct(ty) {
+                self.all_defaultable(&owner, list_of(ty, "properties"))
+            } else {
+                self.is_defaultable(&owner, ty)
+            };
+            self.defaultable.insert(key, result);
+            result
+        }
+
+        fn all_defaultable(&mut self, domain: &str, fields: &[Value]) -> bool {
+            fields.iter().all(|f| {
+                f.get("optional")
+                    .and_then(Value::as_bool)
+                    .unwrap_or_default()
+                    || self.is_defaultable(domain, f)
+            })
+        }
+
+        fn rust_type(&self, item: &Value, inline_enum: &str) -> String {
+            if let Some(reference) = item.get("$ref").and_then(Value::as_str) {
+                return match reference.split_once('.') {
+                    Some((other, id)) => format!("super::{}::{id}", snake_case(other)),
+                    None => reference.to_string(),
+                };
+            }
+            match str_of(item, "type") {
+                "array" if item.get("enum").is_some() => format!("Vec<{inline_enum}>"),
+                _ if item.get("enum").is_some() => inline_enum.to_string(),
+                "integer" => "i64".to_string(),
+                "number" => "f64".to_string(),
+                "boolean" => "bool".to_string(),
+                "string" | "binary" => "String".to_string(),
+                "array" => {
+                    let items = item.get("items").unwrap_or(&Value::Null);
+                    format!("Vec<{}>", self.rust_type(items, inline_enum))
+                }
+                "object" => "serde_json::Map<String, serde_json::Value>".to_string(),
+                _ => "serde_json::Value".to_string(),
+            }
+        }
+
+        fn generate(mut self) -> String {
+            let mut out = String::new();
+            let mut accessors = String::new();
+            for domain in self.domains {
+                let name = str_of(domain, "domain");
+                let module = snake_case(name);
+                let handle = format!("{}Domain", pascal_case(name));
+                doc(&mut out, "", domain);
+                writeln!(out, "pub mod {module} {{").unwrap();
+                self.domain(&mut out, domain, &handle);
+                writeln!(out, "}}\n").unwrap();
+
+                writeln!(
+                    accessors,
+                    "    /// Commands and events of the `{name}` domain.\n    pub fn {module}(&self) -> {module}::{handle}<'_> {{\n        {module}::{handle} {{ client: self }}\n    }}\n"
+                )
+                .unwrap();
+            }
+            writeln!(out, "impl DevToolsClient {{\n{accessors}}}").unwrap();
+            out
+        }
+
+        fn domain(&mut self, out: &mut String, domain: &Value, handle: &str) {
+            let name = str_of(domain, "domain");
+            let mut names: HashSet<String> = list_of(domain, "types")
+                .iter()
+                .map(|t| str_of(t, "id").to_string())
+                .collect();
+            let mut unique = |base: String| {
+                let mut name = base.clone();
+                let mut i = 2;
+                while !names.insert(name.clone()) {
+                    name = format!("{base}{i}");
+                    i += 1;
+                }
+                name
+            };
+
+            for ty in list_of(domain, "types") {
+                let id = str_of(ty, "id");
+                if let Some(values) = ty.get("enum") {
+                    Self::enumeration(out, ty, id, values);
+                } else if Self::is_struct(ty) {
+                    let key = format!("{name}.{id}");
+                    self.structure(
+                        out,
+                        name,
+                        ty,
+                        id,
+                        list_of(ty, "properties"),
+                        Some(&key),
+                        &mut unique,
+                    );
+                } else {
+                    doc(out, "    ", ty);
+                    let alias = self.rust_type(ty, "serde_json::Value");
+                    writeln!(out, "    pub type {id} = {alias};\n").unwrap();
+                }
+            }
+
+            let mut methods = String::new();
+            for command in list_of(domain, "commands") {
+                let method = str_of(command, "name");
+                let pascal = pascal_case(method);
+                let params = unique(format!("{pascal}Params"));
+                let returns = unique(format!("{pascal}Returns"));
+                let parameters = list_of(command, "parameters");
+                self.structure(out, name, command, &params, parameters, None, &mut unique);
+                self.structure(
+                    out,
+                    name,
+                    command,
+                    &returns,
+                    list_of(command, "returns"),
+                    None,
+                    &mut unique,
+                );
+                writeln!(
+                    out,
+                    "    impl super::Command for {params} {{\n        const METHOD: &'static str = \"{name}.{method}\";\n        type Returns = {returns};\n    }}\n"
+                )
+                .unwrap();
+
+                doc(&mut methods, "        ", command);
+                let function = snake_case(method);
+                if parameters.is_empty() {
+                    writeln!(
+                        methods,
+                        "        pub fn {function}(&self) -> super::CdpResponse<{returns}> {{\n            self.client.send({params} {{}})\n        }}\n"
+                    )
+                    .unwrap();
+                } else {
+                    writeln!(
+                        methods,
+                        "        pub fn {function}(&self, params: {params}) -> super::CdpResponse<{returns}> {{\n            self.client.send(params)\n        }}\n"
+                    )
+                    .unwrap();
+                }
+            }
+
+            for event in list_of(domain, "events") {
+                let method = str_of(event, "name");
+                let ident = unique(format!("{}Event", pascal_case(method)));
+                self.structure(
+                    out,
+                    name,
+                    event,
+                    &ident,
+                    list_of(event, "parameters"),
+                    None,
+                    &mut unique,
+                );
+                writeln!(
+                    out,
+                    "    impl super::Event for {ident} {{\n        const NAME: &'static str = \"{name}.{method}\";\n    }}\n"
+                )
+                .unwrap();
+
+                doc(&mut methods, "        ", event);
+                let function = snake_case(&format!("on_{method}"));
+                writeln!(
+                    methods,
+                    "        pub fn {function}<F>(&self, listener: F)\n        where\n            F: Fn({ident}) + Send + Sync + 'static,\n        {{\n            self.client.on(listener)\n        }}\n"
+                )
+                .unwrap();
+            }
+
+            writeln!(
+                out,
+                "    /// Commands and events of the `{name}` domain.\n    #[derive(Clone, Copy)]\n    pub struct {handle}<'a> {{\n        pub(crate) client: &'a super::DevToolsClient,\n    }}\n\n    impl {handle}<'_> {{\n{methods}    }}"
+            )
+            .unwrap();
+        }
+
+        fn enumeration(out: &mut String, item: &Value, ident: &str, values: &Value) {
+            doc(out, "    ", item);
+            writeln!(
+                out,
+                "    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]\n    pub enum {ident} {{"
+            )
+            .unwrap();
+            let mut variants = HashSet::new();
+            for value in values.as_array().into_iter().flatten() {
+                let value = value.as_str().unwrap_or_default();
+                let mut variant = pascal_case(value);
+                while !variants.insert(variant.clone()) {
+                    variant.push('_');
+                }
+                writeln!(
+                    out,
+                    "        #[serde(rename = {value:?})]\n        {variant},"
+                )
+                .unwrap();
+            }
+            writeln!(out, "    }}\n").unwrap();
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        fn structure(
+            &mut self,
+            out: &mut String,
+            domain: &str,
+            item: &Value,
+            ident: &str,
+            fields: &[Value],
+            key: Option<&str>,
+            unique: &mut impl FnMut(String) -> String,
+        ) {
+            let prefix = ident
+                .strip_suffix("Params")
+                .or_else(|| ident.strip_suffix("Returns"))
+                .or_else(|| ident.strip_suffix("Event"))
+                .unwrap_or(ident)
+                .to_string();
+
+            let mut enums = Vec::new();
+            let mut body = String::new();
+            for field in fields {
+                let name = str_of(field, "name");
+                let items = field.get("items").unwrap_or(&Value::Null);
+                let inline_enum = if field.get("enum").is_some() || items.get("enum").is_some() {
+                    let ident = unique(format!("{prefix}{}", pascal_case(name)));
+                    let values = field.get("enum").or(items.get("enum")).unwrap();
+                    enums.push((field, ident.clone(), values));
+                    ident
+                } else {
+                    String::new()
+                };
+
+                let mut ty = self.rust_type(field, &inline_enum);
+                let recursive = match (key, field.get("$ref").and_then(Value::as_str)) {
+                    (Some(key), Some(reference)) => {
+                        self.reaches(&Self::qualify(domain, reference), key, &mut HashSet::new())
+                    }
+                    _ => false,
+                };
+                if recursive {
+                    ty = format!("Box<{ty}>");
+                }
+
+                doc(&mut body, "        ", field);
+                writeln!(body, "        #[serde(rename = {name:?})]").unwrap();
+                if field
+                    .get("optional")
+                    .and_then(Value::as_bool)
+                    .unwrap_or_default()
+                {
+                    writeln!(
+                        body,
+                        "        #[serde(default, skip_serializing_if = \"Option::is_none\")]\n        pub {}: Option<{ty}>,",
+                        snake_case(name)
+                    )
+                    .unwrap();
+                } else {
+                    writeln!(body, "        pub {}: {ty},", snake_case(name)).unwrap();
+                }
+            }
+
+            for (field, ident, values) in enums {
+                Self::enumeration(out, field, &ident, values);
+            }
+
+            let default = if self.all_defaultable(domain, fields) {
+                ", Default"
+            } else {
+                ""
+            };
+            doc(out, "    ", item);
+            writeln!(
+                out,
+                "    #[derive(Debug, Clone{default}, serde::Serialize, serde::Deserialize)]\n    pub struct {ident} {{\n{body}    }}\n"
+            )
+            .unwrap();
+        }
+    }
+}
@@ -217,3 +217,447 @@ pub fn wrapper(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// The parameters of `sig`, named after their pattern, or `arg{index}` for other patterns.
+fn typed_inputs(sig: &syn::Signature) -> Vec<(String, &syn::Type)> {
+    sig.inputs
+        .iter()
+        .enumerate()
+        .filter_map(|(index, arg)| match arg {
+            syn::FnArg::Typed(PatType { pat, ty, .. }) => {
+                let name = match &**pat {
+                    Pat::Ident(pat_ident) => pat_ident.ident.to_string(),
+                    _ => format!("arg{index}"),
+                };
+                Some((name, &**ty))
+            }
+            syn::FnArg::Receiver(_) => None,
+        })
+        .collect()
+}
+
+/// The last path segment of `ty`, if it's a plain path.
+fn last_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+    match ty {
+        syn::Type::Path(TypePath { qself: None, path }) => path.segments.last(),
+        _ => None,
+    }
+}
+
+/// The TypeScript type of the JS values `ty` converts from and to. Unknown types are `unknown`.
+fn ts_type(ty: &syn::Type) -> String {
+    let array = |element: &syn::Type| {
+        let element = ts_type(element);
+        if element.contains(' ') {
+            format!("({element})[]")
+        } else {
+            format!("{element}[]")
+        }
+    };
+    match ty {
+        syn::Type::Reference(reference) => ts_type(&reference.elem),
+        syn::Type::Paren(paren) => ts_type(&paren.elem),
+        syn::Type::Group(group) => ts_type(&group.elem),
+        syn::Type::Slice(slice) => array(&slice.elem),
+        syn::Type::Array(elements) => array(&elements.elem),
+        syn::Type::Tuple(tuple) if tuple.elems.is_empty() => "void".to_string(),
+        syn::Type::Tuple(tuple) => {
+            let elements: Vec<String> = tuple.elems.iter().map(ts_type).collect();
+            format!("[{}]", elements.join(", "))
+        }
+        _ => {
+            let Some(segment) = last_segment(ty) else {
+                return "unknown".to_string();
+            };
+            let arguments: Vec<&syn::Type> = match &segment.arguments {
+                syn::PathArguments::AngleBracketed(arguments) => arguments
+                    .args
+                    .iter()
+                    .filter_map(|argument| match argument {
+                        syn::GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            match (segment.ident.to_string().as_str(), arguments.as_slice()) {
+                ("bool", []) => "boolean".to_string(),
+                (
+                    "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
+                    | "u128" | "usize" | "f32" | "f64",
+                    [],
+                ) => "number".to_string(),
+                ("String" | "str" | "char" | "CefString", []) => "string".to_string(),
+                ("Option", [inner]) => format!("{} | null", ts_type(inner)),
+                ("Result", [ok, ..]) | ("Box" | "Rc" | "Arc", [ok]) => ts_type(ok),
+                ("Vec" | "VecDeque" | "HashSet" | "BTreeSet", [element]) => array(element),
+                ("HashMap" | "BTreeMap", [_, value, ..]) => {
+                    format!("Record<string, {}>", ts_type(value))
+                }
+                _ => "unknown".to_string(),
+            }
+        }
+    }
+}
+
+/// `::cef::TsParam`s describing `inputs`, `Option`s being optional.
+fn ts_params(inputs: &[(String, &syn::Type)]) -> Vec<proc_macro2::TokenStream> {
+    inputs
+        .iter()
+        .map(|(name, ty)| {
+            let ts_type = ts_type(ty);
+            let optional = last_segment(ty).is_some_and(|segment| segment.ident == "Option");
+            quote! {
+                ::cef::TsParam {
+                    name: #name,
+                    ty: #ts_type,
+                    optional: #optional,
+                }
+            }
+        })
+        .collect()
+}
+
+/// The TypeScript type of the value returned by a function returning `output`.
+fn ts_return_type(output: &ReturnType) -> String {
+    match output {
+        ReturnType::Default => "void".to_string(),
+        ReturnType::Type(_, ty) => ts_type(ty),
+    }
+}
+
+/// Options of `#[js_function]`.
+struct JsFunctionArgs {
+    name: Option<syn::LitStr>,
+}
+
+impl Parse for JsFunctionArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut name = None;
+        while !input.is_empty() {
+            let key: syn::Ident = input.parse()?;
+            input.parse::<syn::Token![=]>()?;
+            if key == "name" {
+                name = Some(input.parse()?);
+            } else {
+                return Err(syn::Error::new(key.span(), "expected `name`"));
+            }
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+        Ok(JsFunctionArgs { name })
+    }
+}
+
+/// Expose a free function to JavaScript.
+///
+/// Generates a type with the same name as the function, next to it, with an associated
+/// `js_function() -> cef::JsFunction` to pass to `cef::register_js_functions`.
+/// The JS name defaults to the Rust name and can be set with `#[js_function(name = "...")]`.
+/// The TypeScript types of the parameters and return value are recorded for
+/// `cef::TypeScriptDeclarations`.
+#[proc_macro_attribute]
+pub fn js_function(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let JsFunctionArgs { name } = parse_macro_input!(attr as JsFunctionArgs);
+    let func = parse_macro_input!(item as syn::ItemFn);
+    let sig = &func.sig;
+
+    let unsupported = if let Some(receiver) = sig.receiver() {
+        Some(syn::Error::new_spanned(
+            receiver,
+            "methods can't be js functions",
+        ))
+    } else if let Some(asyncness) = &sig.asyncness {
+        Some(syn::Error::new_spanned(
+            asyncness,
+            "async fns can't be js functions",
+        ))
+    } else if !sig.generics.params.is_empty() {
+        Some(syn::Error::new_spanned(
+            &sig.generics,
+            "generic fns can't be js functions",
+        ))
+    } else {
+        None
+    };
+    if let Some(error) = unsupported {
+        return error.to_compile_error().into();
+    }
+
+    let vis = &func.vis;
+    let ident = &sig.ident;
+    let js_name = name.map_or_else(|| ident.to_string(), |name| name.value());
+    let inputs = typed_inputs(sig);
+    let arity = inputs.len();
+    let args = inputs.iter().enumerate().map(|(index, (name, _))| {
+        quote! { ::cef::js_argument(#js_name, args, #index, #name)? }
+    });
+    let params = ts_params(&inputs);
+    let returns = ts_return_type(&sig.output);
+
+    let expanded = quote! {
+        #func
+
+        #[doc = concat!("JavaScript binding of [`", stringify!(#ident), "()`].")]
+        #[allow(non_camel_case_types)]
+        #vis struct #ident {}
+
+        impl #ident {
+            /// The function to pass to `cef::register_js_functions`.
+            pub fn js_function() -> ::cef::JsFunction {
+                fn call(
+                    args: &[::cef::V8Value],
+                ) -> ::std::result::Result<::cef::V8Value, ::std::string::String> {
+                    ::cef::check_js_arity(#js_name, #arity, args)?;
+                    ::cef::JsReturn::into_js_return(#ident(#(#args),*))
+                }
+
+                ::cef::JsFunction {
+                    name: #js_name,
+                    arity: #arity,
+                    call,
+                    params: &[#(#params),*],
+                    returns: #returns,
+                }
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// Options of `#[command]`.
+struct CommandArgs {
+    name: Option<syn::LitStr>,
+    origins: Vec<syn::LitStr>,
+}
+
+impl Parse for CommandArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut name = None;
+        let mut origins = Vec::new();
+        while !input.is_empty() {
+            let key: syn::Ident = input.parse()?;
+            input.parse::<syn::Token![=]>()?;
+            if key == "name" {
+                name = Some(input.parse()?);
+            } else if key == "origins" {
+                let content;
+                syn::bracketed!(content in input);
+                let list =
+                    content.parse_terminated(<syn::LitStr as Parse>::parse, syn::Token![,])?;
+                origins.extend(list);
+            } else {
+                return Err(syn::Error::new(key.span(), "expected `name` or `origins`"));
+            }
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+        Ok(CommandArgs { name, origins })
+    }
+}
+
+/// `true` if `output` is spelled as a `Result`, e.g. `Result<T, E>` or `anyhow::Result<T>`.
+fn returns_result(output: &ReturnType) -> bool {
+    match output {
+        ReturnType::Type(_, ty) => match &**ty {
+            syn::Type::Path(TypePath { path, .. }) => path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Result"),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    }
+}
+
+/// Expose a free function of the browser process to `cef.invoke` in JavaScript.
+///
+/// Generates a type with the same name as the function, next to it, with an associated
+/// `command() -> cef::Command` to pass to `cef::Commands::command`.
+/// The command name defaults to the Rust name and can be set with `#[command(name = "...")]`,
+/// and the origins allowed to invoke it are set with `#[command(origins = ["..."])]`.
+/// The function may be async, in which case its future must be `Send`. The TypeScript types of
+/// the parameters and return value are recorded for `cef::TypeScriptDeclarations`.
+#[proc_macro_attribute]
+pub fn command(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let CommandArgs { name, origins } = parse_macro_input!(attr as CommandArgs);
+    let func = parse_macro_input!(item as syn::ItemFn);
+    let sig = &func.sig;
+
+    let unsupported = if let Some(receiver) = sig.receiver() {
+        Some(syn::Error::new_spanned(
+            receiver,
+            "methods can't be commands",
+        ))
+    } else if !sig.generics.params.is_empty() {
+        Some(syn::Error::new_spanned(
+            &sig.generics,
+            "generic fns can't be commands",
+        ))
+    } else {
+        None
+    };
+    if let Some(error) = unsupported {
+        return error.to_compile_error().into();
+    }
+
+    let vis = &func.vis;
+    let ident = &sig.ident;
+    let command_name = name.map_or_else(|| ident.to_string(), |name| name.value());
+    let inputs = typed_inputs(sig);
+    let (bindings, idents): (Vec<_>, Vec<_>) = inputs
+        .iter()
+        .enumerate()
+        .map(|(index, (name, _))| {
+            let binding = quote::format_ident!("__arg{}", index);
+            // The type is inferred from the call.
+            let statement = quote! {
+                let #binding = ::cef::command_argument(#command_name, &args, #name)?;
+            };
+            (statement, binding)
+        })
+        .unzip();
+    let call = if sig.asyncness.is_some() {
+        quote! { #ident(#(#idents),*).await }
+    } else {
+        quote! { #ident(#(#idents),*) }
+    };
+    let params = ts_params(&inputs);
+    let returns = ts_return_type(&sig.output);
+    let convert = if returns_result(&sig.output) {
+        quote! { ::cef::command_result }
+    } else {
+        quote! { ::cef::command_output }
+    };
+
+    let expanded = quote! {
+        #func
+
+        #[doc = concat!("Command binding of [`", stringify!(#ident), "()`].")]
+        #[allow(non_camel_case_types)]
+        #vis struct #ident {}
+
+        impl #ident {
+            /// The command to pass to `cef::Commands::command`.
+            pub fn command() -> ::cef::Command {
+                fn call(args: ::cef::CommandArgs) -> ::cef::CommandFuture {
+                    ::std::boxed::Box::pin(async move {
+                        #(#bindings)*
+                        #convert(#call)
+                    })
+                }
+
+                ::cef::Command {
+                    name: #command_name,
+                    origins: &[#(#origins),*],
+                    call,
+                    params: &[#(#params),*],
+                    returns: #returns,
+                }
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// Implement `cef::IpcMessage` for a serde type.
+///
+/// The message name defaults to the type name and can be set with `#[ipc(name = "...")]`.
+#[proc_macro_derive(IpcMessage, attributes(ipc))]
+pub fn derive_ipc_message(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let ident = &input.ident;
+
+    let mut name = ident.to_string();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("ipc"))
+    {
+        let parsed = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = meta.value()?.parse::<syn::LitStr>()?.value();
+                Ok(())
+            } else {
+                Err(meta.error("expected `name`"))
+            }
+        });
+        if let Err(error) = parsed {
+            return error.to_compile_error().into();
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::cef::IpcMessage for #ident #ty_generics #where_clause {
+            const NAME: &'static str = #name;
+        }
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ts_params, ts_return_type, ts_type, typed_inputs};
+
+    fn ts(ty: &str) -> String {
+        ts_type(&syn::parse_str(ty).unwrap())
+    }
+
+    #[test]
+    fn primitive_types() {
+        assert_eq!(ts("bool"), "boolean");
+        assert_eq!(ts("u64"), "number");
+        assert_eq!(ts("f32"), "number");
+        assert_eq!(ts("&str"), "string");
+        assert_eq!(ts("cef::CefString"), "string");
+        assert_eq!(ts("()"), "void");
+        assert_eq!(ts("(i32, String)"), "[number, string]");
+        assert_eq!(ts("cef::V8Value"), "unknown");
+    }
+
+    #[test]
+    fn generic_types() {
+        assert_eq!(ts("Option<String>"), "string | null");
+        assert_eq!(ts("Vec<i32>"), "number[]");
+        assert_eq!(ts("&[u8]"), "number[]");
+        assert_eq!(ts("Vec<Option<i32>>"), "(number | null)[]");
+        assert_eq!(ts("HashMap<String, bool>"), "Record<string, boolean>");
+        assert_eq!(
+            ts("std::collections::BTreeMap<String, Vec<f64>>"),
+            "Record<string, number[]>"
+        );
+        assert_eq!(ts("Result<Vec<String>, std::io::Error>"), "string[]");
+        assert_eq!(ts("anyhow::Result<u8>"), "number");
+        assert_eq!(ts("Box<Option<bool>>"), "boolean | null");
+        assert_eq!(ts("Option<Point>"), "unknown | null");
+    }
+
+    #[test]
+    fn parameters() {
+        let func: syn::ItemFn =
+            syn::parse_str("fn f(a: i32, b: Option<String>, (c, d): (u8, u8)) -> bool { true }")
+                .unwrap();
+        let inputs = typed_inputs(&func.sig);
+        let names: Vec<&str> = inputs.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "arg2"]);
+        let params: Vec<String> = ts_params(&inputs)
+            .iter()
+            .map(|param| param.to_string().replace(' ', ""))
+            .collect();
+        assert_eq!(
+            params,
+            [
+                r#"::cef::TsParam{name:"a",ty:"number",optional:false,}"#,
+                r#"::cef::TsParam{name:"b",ty:"string|null",optional:true,}"#,
+                r#"::cef::TsParam{name:"arg2",ty:"[number,number]",optional:false,}"#,
+            ]
+        );
+        assert_eq!(ts_return_type(&func.sig.output), "boolean");
+        assert_eq!(ts_return_type(&syn::ReturnType::Default), "void");
+    }
+}
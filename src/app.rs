@@ -256,18 +256,29 @@ pub trait CefRenderProcessHandler: Sized {
     }
 
     /// See [cef_render_process_handler_t::on_uncaught_exception] for more documentation.
+    ///
+    /// Only called when [CefSettings::uncaught_exception_stack_size](crate::CefSettings::uncaught_exception_stack_size) is non-zero,
+    /// `stack_trace` holding at most that many frames.
     fn on_uncaught_exception(
         &self,
         browser: crate::CefBrowser,
         frame: crate::CefFrame,
         context: crate::v8::CefV8Context,
-        exception: crate::v8::V8Excepction,
+        exception: crate::v8::V8Exception,
         stack_trace: crate::v8::V8StackTrace,
     ) {
     }
 
-    // See [cef_render_process_handler_t::on_focused_node_changed] for more documentation.
-    // fn on_focused_node_changed(&self, browser: *mut _cef_browser_t, frame: *mut _cef_frame_t, node: *mut _cef_domnode_t);
+    /// See [cef_render_process_handler_t::on_focused_node_changed] for more documentation.
+    ///
+    /// `node` is `None` if no specific node has gained focus.
+    fn on_focused_node_changed(
+        &self,
+        browser: crate::CefBrowser,
+        frame: crate::CefFrame,
+        node: Option<crate::CefDomNode>,
+    ) {
+    }
 
     /// See [cef_render_process_handler_t::on_process_message_received] for more documentation.
     fn on_process_message_received(
@@ -313,9 +324,10 @@ pub trait CefRenderProcessHandler: Sized {
             browser: *mut _cef_browser_t,
         ) {
             let handler: &crate::rc::RcImpl<_, I> = crate::rc::RcImpl::get(self_);
-            handler
-                .interface
-                .on_browser_destroyed(CefBrowser::from(browser));
+            let browser = CefBrowser::from(browser);
+            #[cfg(feature = "serde")]
+            crate::cancel_rpc_calls_for_browser(&browser);
+            handler.interface.on_browser_destroyed(browser);
         }
 
         unsafe extern "C" fn get_load_handler<I: CefRenderProcessHandler>(
@@ -350,11 +362,14 @@ pub trait CefRenderProcessHandler: Sized {
             context: *mut _cef_v8context_t,
         ) {
             let handler: &crate::rc::RcImpl<_, I> = crate::rc::RcImpl::get(self_);
-            handler.interface.on_context_released(
-                CefBrowser::from(browser),
-                crate::CefFrame::from(frame),
-                crate::v8::CefV8Context::from(context),
-            );
+            let context = crate::v8::CefV8Context::from(context);
+            let frame = crate::CefFrame::from(frame);
+            crate::v8::context_released(&context);
+            #[cfg(feature = "serde")]
+            crate::cancel_rpc_calls_for_frame(&frame);
+            handler
+                .interface
+                .on_context_released(CefBrowser::from(browser), frame, context);
         }
 
         unsafe extern "C" fn on_uncaught_exception<I: CefRenderProcessHandler>(
@@ -370,26 +385,24 @@ pub trait CefRenderProcessHandler: Sized {
                 CefBrowser::from(browser),
                 CefFrame::from(frame),
                 crate::v8::CefV8Context::from(context),
-                crate::v8::V8Excepction::from(exception),
+                crate::v8::V8Exception::from(exception),
                 crate::v8::V8StackTrace::from(stack_trace),
             );
         }
 
-        /*
-                unsafe extern "C" fn on_focused_node_changed<I: CefRenderProcessHandler>(
-                    self_: *mut _cef_render_process_handler_t,
-                    browser: *mut _cef_browser_t,
-                    frame: *mut _cef_frame_t,
-                    node: *mut _cef_domnode_t,
-                ) {
-                    let handler: &crate::rc::RcImpl<_, I> = crate::rc::RcImpl::get(self_);
-                    handler.interface.on_focused_node_changed(
-                        CefBrowser::from(browser),
-                        CefFrame::from(frame),
-                        node,
-                    );
-                }
-        */
+        unsafe extern "C" fn on_focused_node_changed<I: CefRenderProcessHandler>(
+            self_: *mut _cef_render_process_handler_t,
+            browser: *mut _cef_browser_t,
+            frame: *mut _cef_frame_t,
+            node: *mut _cef_domnode_t,
+        ) {
+            let handler: &crate::rc::RcImpl<_, I> = crate::rc::RcImpl::get(self_);
+            handler.interface.on_focused_node_changed(
+                CefBrowser::from(browser),
+                CefFrame::from(frame),
+                (!node.is_null()).then(|| crate::CefDomNode::from(node)),
+            );
+        }
 
         unsafe extern "C" fn on_process_message_received<I: CefRenderProcessHandler>(
             self_: *mut _cef_render_process_handler_t,
@@ -414,7 +427,7 @@ pub trait CefRenderProcessHandler: Sized {
         handler.on_context_created = Some(on_context_created::<Self>);
         handler.on_context_released = Some(on_context_released::<Self>);
         handler.on_uncaught_exception = Some(on_uncaught_exception::<Self>);
-        //handler.on_focused_node_changed = Some(on_focused_node_changed::<Self>);
+        handler.on_focused_node_changed = Some(on_focused_node_changed::<Self>);
         handler.on_process_message_received = Some(on_process_message_received::<Self>);
 
         crate::rc::RcImpl::new(handler, self).cast()
@@ -1,6 +1,6 @@
 use crate::{
     client::CefClient, error::Result, net::CefRequestContext, prelude::*, string::CefString,
-    view::CefWindowInfo, CefBrowserView, CefState,
+    view::CefWindowInfo, CefBrowserView, CefPoint, CefState,
 };
 use cef_sys::{
     cef_browser_host_create_browser_sync, cef_browser_settings_t, cef_browser_t,
@@ -8,7 +8,9 @@ use cef_sys::{
 use std::{ffi::c_int, ptr::null_mut};
 
 mod frame;
+mod navigation;
 pub use frame::*;
+pub use navigation::*;
 
 /// See [cef_browser_settings_t] for more documentation.
 #[derive(Debug, Clone)]
@@ -443,11 +445,11 @@ impl CefBrowserHost {
         fn has_view(&self) -> bool;
 
         /// See [cef_browser_host_t::get_client]
-        ///fn get_client<C: CefClient>(&self) -> Option<C> {
-        ///     get_client.map(|f| unsafe {
-        ///        f(self.get_this())
-        ///    })
-        ///}
+        ///
+        /// Returns `None` if the browser's client wasn't created from a `C`.
+        fn get_client<C: CefClient>(&self) -> crate::CefClientRef<C> {
+            get_client.and_then(|f| unsafe { crate::CefClientRef::downcast(f(self.get_this())) })
+        }
 
         /// See [cef_browser_host_t::get_request_context]
         fn get_request_context(&self) -> CefRequestContext {
@@ -464,6 +466,84 @@ impl CefBrowserHost {
 
         /// See [cef_browser_host_t::notify_screen_info_changed]
         fn notify_screen_info_changed(&self);
+
+        /// See [cef_browser_host_t::show_dev_tools]
+        ///
+        /// If `inspect_element_at` is set, the element at that position will be inspected.
+        fn show_dev_tools<T: CefClient>(
+            &self,
+            window_info: &CefWindowInfo,
+            client: Option<T>,
+            settings: CefBrowserSettings,
+            inspect_element_at: Option<CefPoint>,
+        ) {
+            let client = client.map(|c| c.into_raw()).unwrap_or(null_mut());
+            let inspect_element_at = inspect_element_at
+                .as_ref()
+                .map(std::ptr::from_ref)
+                .unwrap_or(std::ptr::null());
+            show_dev_tools.map(|f| unsafe {
+                f(
+                    self.get_this(),
+                    &window_info.as_raw(),
+                    client,
+                    &settings.as_raw(),
+                    inspect_element_at,
+                )
+            })
+        }
+
+        /// See [cef_browser_host_t::close_dev_tools]
+        fn close_dev_tools(&self);
+
+        /// See [cef_browser_host_t::has_dev_tools]
+        fn has_dev_tools(&self) -> bool;
+
+        /// See [cef_browser_host_t::send_dev_tools_message]
+        fn send_dev_tools_message(&self, message: &[u8]) -> bool {
+            send_dev_tools_message.map(|f| unsafe {
+                f(self.get_this(), message.as_ptr().cast(), message.len()) == 1
+            })
+        }
+
+        /// See [cef_browser_host_t::execute_dev_tools_method]
+        fn execute_dev_tools_method(
+            &self,
+            message_id: i32,
+            method: &str,
+            params: Option<crate::CefDictionaryValue>,
+        ) -> i32 {
+            let params = params.map(|p| unsafe { p.into_raw() }).unwrap_or(null_mut());
+            execute_dev_tools_method.map(|f| unsafe {
+                f(self.get_this(), message_id, &CefString::from(method).as_raw(), params)
+            })
+        }
+
+        /// See [cef_browser_host_t::add_dev_tools_message_observer]
+        fn add_dev_tools_message_observer<T: crate::CefDevToolsMessageObserver>(
+            &self,
+            observer: T,
+        ) -> crate::CefRegistration {
+            add_dev_tools_message_observer.and_then(|f| unsafe {
+                let registration = f(self.get_this(), observer.into_raw());
+                if registration.is_null() {
+                    None
+                } else {
+                    Some(crate::CefRegistration::from(registration))
+                }
+            })
+        }
+        /// See [cef_browser_host_t::get_visible_navigation_entry]
+        fn get_visible_navigation_entry(&self) -> CefNavigationEntry {
+            get_visible_navigation_entry.and_then(|f| unsafe {
+                let entry = f(self.get_this());
+                if entry.is_null() {
+                    None
+                } else {
+                    Some(CefNavigationEntry::from(entry))
+                }
+            })
+        }
     /*
         /// See [cef_browser_host_t::can_zoom]
         fn can_zoom(&self, command: cef_zoom_command_t) -> bool;
@@ -501,27 +581,6 @@ impl CefBrowserHost {
         /// See [cef_browser_host_t::stop_finding]
         fn stop_finding(&self, clear_selection: bool);
 
-        /// See [cef_browser_host_t::show_dev_tools]
-        ///fn show_dev_tools(&self, window_info: &CefWindowInfo, client: CefClient, settings: &CefBrowserSettings, inspect_element_at: &CefPoint);
-
-        /// See [cef_browser_host_t::close_dev_tools]
-        fn close_dev_tools(&self);
-
-        /// See [cef_browser_host_t::has_dev_tools]
-        fn has_dev_tools(&self) -> bool;
-
-        /// See [cef_browser_host_t::send_dev_tools_message]
-        fn send_dev_tools_message(&self, message: &[u8]) -> bool;
-
-        /// See [cef_browser_host_t::execute_dev_tools_method]
-        ///fn execute_dev_tools_method(&self, message_id: i32, method: &str, params: Option<crate::CefDictionaryValue>) -> i32;
-
-        /// See [cef_browser_host_t::add_dev_tools_message_observer]
-        ///fn add_dev_tools_message_observer(&self, observer: CefDevToolsMessageObserver) -> CefRegistration;
-
-        /// See [cef_browser_host_t::get_navigation_entries]
-        ///fn get_navigation_entries(&self, visitor: CefNavigationEntryVisitor, current_only: bool);
-
         /// See [cef_browser_host_t::replace_misspelling]
         fn replace_misspelling(&self, word: &str);
 
@@ -603,13 +662,6 @@ impl CefBrowserHost {
         /// See [cef_browser_host_t::drag_source_system_drag_ended]
         fn drag_source_system_drag_ended(&self);
 
-        /// See [cef_browser_host_t::get_visible_navigation_entry]
-        fn get_visible_navigation_entry(&self) -> Option<CefNavigationEntry> {
-             get_visible_navigation_entry.map(|f| unsafe {
-                CefNavigationEntry::from(f(self.get_this()))
-            })
-        }
-
         /// See [cef_browser_host_t::set_accessibility_state]
         fn set_accessibility_state(&self, state: cef_state_t);
 
@@ -640,4 +692,38 @@ impl CefBrowserHost {
                 /// See [cef_browser_host_t::get_runtime_style]
                 fn get_runtime_style(&self) -> cef_runtime_style_t;
             }
+
+    /// See [cef_browser_host_t::get_navigation_entries]
+    ///
+    /// The visitor is executed synchronously on the UI thread.
+    pub fn visit_navigation_entries<T: CefNavigationEntryVisitor>(
+        &self,
+        visitor: T,
+        current_only: bool,
+    ) -> Option<()> {
+        let Some(inner) = &self.0 else {
+            return None;
+        };
+        inner
+            .get_navigation_entries
+            .map(|f| unsafe { f(self.get_this(), visitor.into_raw(), current_only as _) })
+    }
+
+    /// Collect the navigation history into owned [NavigationEntrySnapshot]s.
+    ///
+    /// If `current_only` is true only the current entry is returned. Must be
+    /// called on the UI thread, otherwise [Error::WrongThread] is returned.
+    pub fn get_navigation_entries(
+        &self,
+        current_only: bool,
+    ) -> Result<Vec<NavigationEntrySnapshot>> {
+        if !crate::task::currently_on(crate::CefThreadId::TID_UI) {
+            return Err(Error::WrongThread(crate::CefThreadId::TID_UI as _));
+        }
+        let entries = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        self.visit_navigation_entries(SnapshotCollector(entries.clone()), current_only);
+        let mut entries = std::mem::take(&mut *entries.lock().unwrap());
+        entries.sort_by_key(|entry| entry.index);
+        Ok(entries)
+    }
 }
@@ -35,10 +35,14 @@ impl CefFrame {
         fn view_source(&self);
 
         /// See [cef_frame_t::get_source]
-        /// fn get_source(&self, visitor: crate::StringVisitor);
+        fn get_source(&self, visitor: impl crate::CefStringVisitor) {
+            get_source.map(|f| unsafe { f(self.get_this(), visitor.into_raw()) })
+        }
 
         /// See [cef_frame_t::get_text]
-        ///fn get_text(&self, visitor: crate::StringVisitor);
+        fn get_text(&self, visitor: impl crate::CefStringVisitor) {
+            get_text.map(|f| unsafe { f(self.get_this(), visitor.into_raw()) })
+        }
 
         /// See [cef_frame_t::load_request]
         fn load_request(&self, request: crate::net::CefRequest);
@@ -121,14 +125,29 @@ impl CefFrame {
         }
 
         /// See [cef_frame_t::visit_dom]
-        // fn visit_dom(&self, visitor: crate::DOMVisitor);
+        ///
+        /// Must be called on the render process main thread.
+        fn visit_dom(&self, visitor: impl crate::CefDomVisitor) {
+            visit_dom.map(|f| unsafe { f(self.get_this(), visitor.into_raw()) })
+        }
 
         /// See [cef_frame_t::create_urlrequest]
-        //fn create_urlrequest(
-        //    &self,
-        //    request: crate::Request,
-        //    client: crate::URLRequestClient,
-        //) -> Option<crate::URLRequest>;
+        ///
+        /// Must be called from the browser process.
+        fn create_urlrequest(
+            &self,
+            request: crate::CefRequest,
+            client: impl crate::CefUrlRequestClient,
+        ) -> crate::CefUrlRequest {
+            create_urlrequest.and_then(|f| unsafe {
+                let url_request = f(self.get_this(), request.into_raw(), client.into_raw());
+                if url_request.is_null() {
+                    None
+                } else {
+                    Some(crate::CefUrlRequest::from(url_request))
+                }
+            })
+        }
 
         /// See [cef_frame_t::send_process_message]
         fn send_process_message(
@@ -141,3 +160,19 @@ impl CefFrame {
         }
     );
 }
+
+impl CefFrame {
+    /// Retrieve this frame's HTML source asynchronously, see [CefFrame::get_source].
+    pub fn get_source_async(&self) -> crate::StringFuture {
+        let (future, sender) = crate::StringFuture::new();
+        self.get_source(sender);
+        future
+    }
+
+    /// Retrieve this frame's display text asynchronously, see [CefFrame::get_text].
+    pub fn get_text_async(&self) -> crate::StringFuture {
+        let (future, sender) = crate::StringFuture::new();
+        self.get_text(sender);
+        future
+    }
+}
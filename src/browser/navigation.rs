@@ -0,0 +1,207 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{prelude::*, CefBaseTime, CefTransitionType};
+
+/// See [cef_navigation_entry_t] for more documentation.
+#[derive(Debug, Clone)]
+#[wrapper]
+pub struct CefNavigationEntry(cef_navigation_entry_t);
+
+impl CefNavigationEntry {
+    wrapper_methods!(
+        /// See [cef_navigation_entry_t::is_valid]
+        fn is_valid(&self) -> bool;
+
+        /// See [cef_navigation_entry_t::get_url]
+        fn get_url(&self) -> CefString {
+            get_url.and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_navigation_entry_t::get_display_url]
+        fn get_display_url(&self) -> CefString {
+            get_display_url
+                .and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_navigation_entry_t::get_original_url]
+        fn get_original_url(&self) -> CefString {
+            get_original_url
+                .and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_navigation_entry_t::get_title]
+        fn get_title(&self) -> CefString {
+            get_title.and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_navigation_entry_t::get_transition_type]
+        fn get_transition_type(&self) -> CefTransitionType;
+
+        /// See [cef_navigation_entry_t::has_post_data]
+        fn has_post_data(&self) -> bool;
+
+        /// See [cef_navigation_entry_t::get_completion_time]
+        fn get_completion_time(&self) -> CefBaseTime;
+
+        /// See [cef_navigation_entry_t::get_http_status_code]
+        fn get_http_status_code(&self) -> i32;
+
+        /// See [cef_navigation_entry_t::get_sslstatus]
+        fn get_sslstatus(&self) -> CefSslStatus {
+            get_sslstatus.and_then(|f| unsafe {
+                let status = f(self.get_this());
+                if status.is_null() {
+                    None
+                } else {
+                    Some(CefSslStatus::from(status))
+                }
+            })
+        }
+    );
+
+    /// Copy every field of this entry into an owned [NavigationEntrySnapshot].
+    ///
+    /// Entries handed to a [CefNavigationEntryVisitor] are only valid for the
+    /// duration of the visit, so take a snapshot to keep them around.
+    pub fn snapshot(&self, current: bool, index: usize) -> NavigationEntrySnapshot {
+        NavigationEntrySnapshot {
+            url: self.get_url().unwrap_or_default(),
+            display_url: self.get_display_url().unwrap_or_default(),
+            original_url: self.get_original_url().unwrap_or_default(),
+            title: self.get_title().unwrap_or_default(),
+            transition_type: self
+                .get_transition_type()
+                .unwrap_or(CefTransitionType::TT_EXPLICIT),
+            has_post_data: self.has_post_data().unwrap_or_default(),
+            completion_time: self.get_completion_time().unwrap_or(CefBaseTime { val: 0 }),
+            http_status_code: self.get_http_status_code().unwrap_or_default(),
+            ssl_status: self.get_sslstatus().map(|status| status.snapshot()),
+            current,
+            index,
+        }
+    }
+}
+
+/// See [cef_sslstatus_t] for more documentation.
+#[derive(Debug, Clone)]
+#[wrapper]
+pub struct CefSslStatus(cef_sslstatus_t);
+
+impl CefSslStatus {
+    wrapper_methods!(
+        /// See [cef_sslstatus_t::is_secure_connection]
+        fn is_secure_connection(&self) -> bool;
+
+        /// See [cef_sslstatus_t::get_cert_status]
+        fn get_cert_status(&self) -> cef_cert_status_t;
+
+        /// See [cef_sslstatus_t::get_sslversion]
+        fn get_sslversion(&self) -> cef_ssl_version_t;
+
+        /// See [cef_sslstatus_t::get_content_status]
+        fn get_content_status(&self) -> cef_ssl_content_status_t;
+    );
+
+    /// Copy the status into an owned [SslStatusSnapshot].
+    pub fn snapshot(&self) -> SslStatusSnapshot {
+        SslStatusSnapshot {
+            is_secure_connection: self.is_secure_connection().unwrap_or_default(),
+            cert_status: self
+                .get_cert_status()
+                .unwrap_or(cef_cert_status_t::CERT_STATUS_NONE),
+            ssl_version: self
+                .get_sslversion()
+                .unwrap_or(cef_ssl_version_t::SSL_CONNECTION_VERSION_UNKNOWN),
+            content_status: self
+                .get_content_status()
+                .unwrap_or(cef_ssl_content_status_t::SSL_CONTENT_NORMAL_CONTENT),
+        }
+    }
+}
+
+/// Owned copy of a [CefSslStatus].
+#[derive(Debug, Clone, Copy)]
+pub struct SslStatusSnapshot {
+    /// See [cef_sslstatus_t::is_secure_connection]
+    pub is_secure_connection: bool,
+    /// See [cef_sslstatus_t::get_cert_status]
+    pub cert_status: cef_cert_status_t,
+    /// See [cef_sslstatus_t::get_sslversion]
+    pub ssl_version: cef_ssl_version_t,
+    /// See [cef_sslstatus_t::get_content_status]
+    pub content_status: cef_ssl_content_status_t,
+}
+
+/// Owned copy of a [CefNavigationEntry], as returned by
+/// [CefBrowserHost::get_navigation_entries](crate::CefBrowserHost::get_navigation_entries).
+#[derive(Debug, Clone)]
+pub struct NavigationEntrySnapshot {
+    /// See [cef_navigation_entry_t::get_url]
+    pub url: CefString,
+    /// See [cef_navigation_entry_t::get_display_url]
+    pub display_url: CefString,
+    /// See [cef_navigation_entry_t::get_original_url]
+    pub original_url: CefString,
+    /// See [cef_navigation_entry_t::get_title]
+    pub title: CefString,
+    /// See [cef_navigation_entry_t::get_transition_type]
+    pub transition_type: CefTransitionType,
+    /// See [cef_navigation_entry_t::has_post_data]
+    pub has_post_data: bool,
+    /// See [cef_navigation_entry_t::get_completion_time]
+    pub completion_time: CefBaseTime,
+    /// See [cef_navigation_entry_t::get_http_status_code]
+    pub http_status_code: i32,
+    /// See [cef_navigation_entry_t::get_sslstatus]
+    pub ssl_status: Option<SslStatusSnapshot>,
+    /// Whether this is the currently loaded navigation entry.
+    pub current: bool,
+    /// 0-based index of this entry in the navigation history.
+    pub index: usize,
+}
+
+/// See [cef_navigation_entry_visitor_t]
+pub trait CefNavigationEntryVisitor: Sized {
+    /// See [cef_navigation_entry_visitor_t::visit]
+    ///
+    /// Return `false` to stop visiting entries.
+    fn visit(&self, entry: CefNavigationEntry, current: bool, index: usize, total: usize) -> bool;
+
+    #[doc(hidden)]
+    fn into_raw(self) -> *mut cef_navigation_entry_visitor_t {
+        unsafe extern "C" fn visit<T: CefNavigationEntryVisitor>(
+            self_: *mut _cef_navigation_entry_visitor_t,
+            entry: *mut _cef_navigation_entry_t,
+            current: ::std::os::raw::c_int,
+            index: ::std::os::raw::c_int,
+            total: ::std::os::raw::c_int,
+        ) -> ::std::os::raw::c_int {
+            let object: &crate::rc::RcImpl<_, T> = crate::rc::RcImpl::get(self_);
+            if entry.is_null() {
+                return 1;
+            }
+            object.interface.visit(
+                CefNavigationEntry::from(entry),
+                current == 1,
+                index as _,
+                total as _,
+            ) as _
+        }
+
+        let mut object: cef_navigation_entry_visitor_t = unsafe { std::mem::zeroed() };
+        object.visit = Some(visit::<Self>);
+        crate::rc::RcImpl::new(object, self).cast()
+    }
+}
+
+/// Visitor collecting [NavigationEntrySnapshot]s into a shared list.
+pub(crate) struct SnapshotCollector(pub(crate) Arc<Mutex<Vec<NavigationEntrySnapshot>>>);
+
+impl CefNavigationEntryVisitor for SnapshotCollector {
+    fn visit(&self, entry: CefNavigationEntry, current: bool, index: usize, _total: usize) -> bool {
+        if let Ok(mut entries) = self.0.lock() {
+            entries.push(entry.snapshot(current, index));
+        }
+        true
+    }
+}
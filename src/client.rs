@@ -1,12 +1,24 @@
+use std::{
+    any::TypeId, collections::BTreeMap, marker::PhantomData, ops::Deref, ptr::NonNull, sync::Mutex,
+};
+
 use crate::handler::*;
 use cef_sys::cef_client_t;
+use cef_wrapper_macro::wrapper;
 
-use crate::{rc::RcImpl, CefBrowser};
+use crate::{
+    rc::{RcImpl, RefGuard},
+    CefBrowser,
+};
 
 /// Handle browser-instance-specific callbacks
 ///
 /// See [cef_client_t] for more documentation.
-pub trait CefClient: Sized {
+///
+/// The request and frame handlers of every client are reserved by this crate. They cancel the
+/// pending IPC calls and [message router](crate::MessageRouterBrowserSide) queries of frames
+/// that navigate or detach, and of browsers whose renderer process terminates.
+pub trait CefClient: Sized + 'static {
     type LifeSpan: CefLifeSpanHandler;
     type Render: CefRenderHandler;
     type ContextMenu: CefContextMenuHandler;
@@ -66,10 +78,7 @@ pub trait CefClient: Sized {
     //     None
     // }
 
-    // /// See [cef_client_t::get_frame_handler]
-    // fn get_frame_handler(&self) -> Option<CefFrameHandler> {
-    //     None
-    // }
+    // The frame handler is reserved, see `handler::lifecycle`.
 
     // /// See [cef_client_t::get_permission_handler]
     // fn get_permission_handler(&self) -> Option<PermissionHandler> {
@@ -96,10 +105,7 @@ pub trait CefClient: Sized {
     //     None
     // }
 
-    // /// See [cef_client_t::get_request_handler]
-    // fn get_request_handler(&self) -> Option<RequestHandler> {
-    //     None
-    // }
+    // The request handler is reserved, see `handler::lifecycle`.
 
     /// See [cef_client_t::on_process_message_received]
     fn on_process_message_received(
@@ -120,27 +126,127 @@ pub trait CefClient: Sized {
         object.get_load_handler = Some(get_load_handler::<Self>);
         //object.get_audio_handler = Some(get_audio_handler::<Self, H>);
         //object.get_focus_handler = Some(get_focus_handler::<Self, H>);
-        //object.get_frame_handler = Some(get_frame_handler::<Self, H>);
+        object.get_frame_handler = Some(get_frame_handler);
         //object.get_print_handler = Some(get_print_handler::<Self, H>);
         //object.get_dialog_handler = Some(get_dialog_handler::<Self, H>);
         object.get_render_handler = Some(get_render_handler::<Self>);
         object.get_life_span_handler = Some(get_life_span_handler::<Self>);
         //object.get_command_handler = Some(get_command_handler::<Self, H>);
         //object.get_display_handler = Some(get_display_handler::<Self, H>);
-        //object.get_request_handler = Some(get_request_handler::<Self, H>);
+        object.get_request_handler = Some(get_request_handler);
         //object.get_download_handler = Some(get_download_handler::<Self, H>);
         //object.get_permission_handler = Some(get_permission_handler::<Self, H>);
         object.get_context_menu_handler = Some(get_context_menu_handler::<Self>);
         object.on_process_message_received = Some(on_process_message_received::<Self>);
 
-        RcImpl::new(object, self).cast()
+        let ptr = RcImpl::new(object, ClientImpl(self));
+        CLIENTS
+            .lock()
+            .unwrap()
+            .insert(ptr as usize, TypeId::of::<Self>());
+        ptr.cast()
+    }
+}
+
+/// Type of every live [cef_client_t] created by [CefClient::into_raw], keyed by address.
+///
+/// Comparing the vtable entries alone isn't enough to downcast: identical
+/// monomorphizations for different client types may be merged into one function.
+static CLIENTS: Mutex<BTreeMap<usize, TypeId>> = Mutex::new(BTreeMap::new());
+
+/// Rust side of a [cef_client_t] created by [CefClient::into_raw].
+pub(crate) struct ClientImpl<C: CefClient>(C);
+
+impl<C: CefClient> Deref for ClientImpl<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<C: CefClient> Drop for ClientImpl<C> {
+    fn drop(&mut self) {
+        let offset = std::mem::offset_of!(RcImpl<cef_client_t, ClientImpl<C>>, interface);
+        let ptr = (self as *const Self as usize) - offset;
+        if let Ok(mut clients) = CLIENTS.lock() {
+            clients.remove(&ptr);
+        }
+    }
+}
+
+/// See [cef_client_t] for more documentation.
+///
+/// A client handed over by CEF, which may or may not have been created from a [CefClient].
+#[derive(Debug, Clone)]
+#[wrapper]
+pub struct CefAnyClient(cef_client_t);
+
+impl CefAnyClient {
+    /// Get a typed handle to the client if it was created from a `C`.
+    pub fn downcast<C: CefClient>(self) -> Option<CefClientRef<C>> {
+        unsafe { CefClientRef::downcast(self.into_raw()) }
+    }
+}
+
+/// Typed handle to the Rust client behind a browser, see [CefBrowserHost::get_client].
+///
+/// The handle keeps the client alive and dereferences to `C`.
+///
+/// [CefBrowserHost::get_client]: crate::CefBrowserHost::get_client
+pub struct CefClientRef<C: CefClient> {
+    inner: RefGuard<cef_client_t>,
+    marker: PhantomData<C>,
+}
+
+impl<C: CefClient> CefClientRef<C> {
+    /// Take ownership of a client reference returned by CEF if it was created from a `C`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be null or a valid [cef_client_t] whose reference the caller owns.
+    pub(crate) unsafe fn downcast(ptr: *mut cef_client_t) -> Option<Self> {
+        let inner = RefGuard::from_raw(NonNull::new(ptr)?);
+        let is_c = CLIENTS.lock().unwrap().get(&(ptr as usize)) == Some(&TypeId::of::<C>());
+        is_c.then_some(Self {
+            inner,
+            marker: PhantomData,
+        })
     }
 }
 
+impl<C: CefClient> Deref for CefClientRef<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        let object: &RcImpl<cef_client_t, ClientImpl<C>> =
+            RcImpl::get(unsafe { self.inner.get_this() });
+        &object.interface
+    }
+}
+
+impl<C: CefClient> Clone for CefClientRef<C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<C: CefClient + std::fmt::Debug> std::fmt::Debug for CefClientRef<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CefClientRef").field(&**self).finish()
+    }
+}
+
+unsafe impl<C: CefClient + Send + Sync> Send for CefClientRef<C> {}
+unsafe impl<C: CefClient + Send + Sync> Sync for CefClientRef<C> {}
+
 unsafe extern "C" fn get_render_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_render_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_render_handler()
         .map(|h| h.into_raw())
@@ -150,7 +256,7 @@ unsafe extern "C" fn get_render_handler<I: CefClient>(
 unsafe extern "C" fn get_life_span_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_life_span_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface.get_life_span_handler().unwrap().into_raw()
     //.map(|h| h.into_raw())
     //.unwrap_or(core::ptr::null_mut())
@@ -159,7 +265,7 @@ unsafe extern "C" fn get_life_span_handler<I: CefClient>(
 pub(crate) unsafe extern "C" fn get_context_menu_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_context_menu_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_context_menu_handler()
         .map(|h| h.into_raw())
@@ -173,7 +279,7 @@ pub(crate) unsafe extern "C" fn on_process_message_received<I: CefClient>(
     source_process: cef_sys::cef_process_id_t,
     message: *mut cef_sys::_cef_process_message_t,
 ) -> ::std::os::raw::c_int {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface.on_process_message_received(
         CefBrowser::from(browser),
         crate::CefFrame::from(frame),
@@ -182,10 +288,24 @@ pub(crate) unsafe extern "C" fn on_process_message_received<I: CefClient>(
     ) as _
 }
 
+/// The crate's own request handler, see [request_handler].
+unsafe extern "C" fn get_request_handler(
+    _self_: *mut cef_sys::cef_client_t,
+) -> *mut cef_sys::cef_request_handler_t {
+    request_handler()
+}
+
+/// The crate's own frame handler, see [frame_handler].
+unsafe extern "C" fn get_frame_handler(
+    _self_: *mut cef_sys::cef_client_t,
+) -> *mut cef_sys::cef_frame_handler_t {
+    frame_handler()
+}
+
 pub(crate) unsafe extern "C" fn get_load_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_load_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_load_handler()
         .map(|h| h.into_raw())
@@ -196,7 +316,7 @@ pub(crate) unsafe extern "C" fn get_load_handler<I: CefClient>(
 pub(crate) unsafe extern "C" fn get_audio_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_audio_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_audio_handler()
         .map(|h| h.into_raw())
@@ -206,7 +326,7 @@ pub(crate) unsafe extern "C" fn get_audio_handler<I: CefClient>(
 pub(crate) unsafe extern "C" fn get_request_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_request_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_request_handler()
         .map(|h| h.into_raw())
@@ -216,7 +336,7 @@ pub(crate) unsafe extern "C" fn get_request_handler<I: CefClient>(
 pub(crate) unsafe extern "C" fn get_keyboard_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_keyboard_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_keyboard_handler()
         .map(|h| h.into_raw())
@@ -226,7 +346,7 @@ pub(crate) unsafe extern "C" fn get_keyboard_handler<I: CefClient>(
 pub(crate) unsafe extern "C" fn get_js_dialog_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_jsdialog_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_jsdialog_handler()
         .map(|h| h.into_raw())
@@ -236,7 +356,7 @@ pub(crate) unsafe extern "C" fn get_js_dialog_handler<I: CefClient>(
 pub(crate) unsafe extern "C" fn get_permission_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_permission_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_permission_handler()
         .map(|h| h.into_raw())
@@ -246,7 +366,7 @@ pub(crate) unsafe extern "C" fn get_permission_handler<I: CefClient>(
 pub(crate) unsafe extern "C" fn get_frame_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_frame_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_frame_handler()
         .map(|h| h.into_raw())
@@ -256,7 +376,7 @@ pub(crate) unsafe extern "C" fn get_frame_handler<I: CefClient>(
 pub(crate) unsafe extern "C" fn get_command_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_command_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_command_handler()
         .map(|h| h.into_raw())
@@ -268,7 +388,7 @@ pub(crate) unsafe extern "C" fn get_command_handler<I: CefClient>(
 pub(crate) unsafe extern "C" fn get_dialog_handler<I: CefClient, H: DialogHandler>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_dialog_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_dialog_handler::<H>()
         .map(|h| h.into_raw())
@@ -278,7 +398,7 @@ pub(crate) unsafe extern "C" fn get_dialog_handler<I: CefClient, H: DialogHandle
 pub(crate) unsafe extern "C" fn get_display_handler<I: CefClient, H: DisplayHandler>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_display_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_display_handler::<H>()
         .map(|h| h.into_raw())
@@ -288,7 +408,7 @@ pub(crate) unsafe extern "C" fn get_display_handler<I: CefClient, H: DisplayHand
 pub(crate) unsafe extern "C" fn get_download_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_download_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_download_handler()
         .map(|h| h.into_raw())
@@ -298,7 +418,7 @@ pub(crate) unsafe extern "C" fn get_download_handler<I: CefClient>(
 pub(crate) unsafe extern "C" fn get_drag_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_drag_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_drag_handler()
         .map(|h| h.into_raw())
@@ -308,7 +428,7 @@ pub(crate) unsafe extern "C" fn get_drag_handler<I: CefClient>(
 pub(crate) unsafe extern "C" fn get_find_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_find_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_find_handler()
         .map(|h| h.into_raw())
@@ -318,7 +438,7 @@ pub(crate) unsafe extern "C" fn get_find_handler<I: CefClient>(
 pub(crate) unsafe extern "C" fn get_focus_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_focus_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_focus_handler()
         .map(|h| h.into_raw())
@@ -330,7 +450,7 @@ pub(crate) unsafe extern "C" fn get_focus_handler<I: CefClient>(
 pub(crate) unsafe extern "C" fn get_print_handler<I: CefClient>(
     self_: *mut cef_sys::cef_client_t,
 ) -> *mut cef_sys::cef_print_handler_t {
-    let obj: &mut RcImpl<_, I> = RcImpl::get(self_);
+    let obj: &mut RcImpl<_, ClientImpl<I>> = RcImpl::get(self_);
     obj.interface
         .get_print_handler()
         .map(|h| h.into_raw())
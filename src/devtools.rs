@@ -0,0 +1,320 @@
+//! DevTools protocol module
+//!
+//! [`CefDevToolsMessageObserver`] is the raw observer of DevTools protocol messages.
+//! [`DevToolsClient`] builds on top of it to send method calls, match their results by message id
+//! and dispatch events to the subscribed listeners.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+};
+
+use serde_json::Value;
+
+use crate::{oneshot, prelude::*, rc::RcImpl, CefBrowser, CefBrowserHost, CefRegistration};
+
+#[cfg(feature = "devtools-protocol")]
+pub mod protocol;
+
+/// See [cef_dev_tools_message_observer_t] for more docs.
+#[allow(unused_variables)]
+pub trait CefDevToolsMessageObserver: Sized {
+    /// See [cef_dev_tools_message_observer_t::on_dev_tools_message]
+    fn on_dev_tools_message(&self, browser: CefBrowser, message: &[u8]) -> bool {
+        false
+    }
+
+    /// See [cef_dev_tools_message_observer_t::on_dev_tools_method_result]
+    fn on_dev_tools_method_result(
+        &self,
+        browser: CefBrowser,
+        message_id: i32,
+        success: bool,
+        result: &[u8],
+    ) {
+    }
+
+    /// See [cef_dev_tools_message_observer_t::on_dev_tools_event]
+    fn on_dev_tools_event(&self, browser: CefBrowser, method: CefString, params: &[u8]) {}
+
+    /// See [cef_dev_tools_message_observer_t::on_dev_tools_agent_attached]
+    fn on_dev_tools_agent_attached(&self, browser: CefBrowser) {}
+
+    /// See [cef_dev_tools_message_observer_t::on_dev_tools_agent_detached]
+    fn on_dev_tools_agent_detached(&self, browser: CefBrowser) {}
+
+    #[doc(hidden)]
+    fn into_raw(self) -> *mut cef_dev_tools_message_observer_t {
+        let mut object: cef_dev_tools_message_observer_t = unsafe { std::mem::zeroed() };
+        object.on_dev_tools_message = Some(on_dev_tools_message::<Self>);
+        object.on_dev_tools_method_result = Some(on_dev_tools_method_result::<Self>);
+        object.on_dev_tools_event = Some(on_dev_tools_event::<Self>);
+        object.on_dev_tools_agent_attached = Some(on_dev_tools_agent_attached::<Self>);
+        object.on_dev_tools_agent_detached = Some(on_dev_tools_agent_detached::<Self>);
+
+        RcImpl::new(object, self).cast()
+    }
+}
+
+unsafe fn raw_bytes<'a>(ptr: *const std::ffi::c_void, size: usize) -> &'a [u8] {
+    if ptr.is_null() || size == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(ptr.cast(), size)
+    }
+}
+
+unsafe extern "C" fn on_dev_tools_message<I: CefDevToolsMessageObserver>(
+    this: *mut cef_dev_tools_message_observer_t,
+    browser: *mut cef_browser_t,
+    message: *const std::ffi::c_void,
+    message_size: usize,
+) -> i32 {
+    let obj: &RcImpl<_, I> = RcImpl::get(this);
+    let browser = CefBrowser::from(browser);
+    obj.interface
+        .on_dev_tools_message(browser, raw_bytes(message, message_size)) as i32
+}
+
+unsafe extern "C" fn on_dev_tools_method_result<I: CefDevToolsMessageObserver>(
+    this: *mut cef_dev_tools_message_observer_t,
+    browser: *mut cef_browser_t,
+    message_id: i32,
+    success: i32,
+    result: *const std::ffi::c_void,
+    result_size: usize,
+) {
+    let obj: &RcImpl<_, I> = RcImpl::get(this);
+    let browser = CefBrowser::from(browser);
+    obj.interface.on_dev_tools_method_result(
+        browser,
+        message_id,
+        success != 0,
+        raw_bytes(result, result_size),
+    );
+}
+
+unsafe extern "C" fn on_dev_tools_event<I: CefDevToolsMessageObserver>(
+    this: *mut cef_dev_tools_message_observer_t,
+    browser: *mut cef_browser_t,
+    method: *const cef_string_t,
+    params: *const std::ffi::c_void,
+    params_size: usize,
+) {
+    let obj: &RcImpl<_, I> = RcImpl::get(this);
+    let browser = CefBrowser::from(browser);
+    let method = CefString::from_raw(method).unwrap_or_default();
+    obj.interface
+        .on_dev_tools_event(browser, method, raw_bytes(params, params_size));
+}
+
+unsafe extern "C" fn on_dev_tools_agent_attached<I: CefDevToolsMessageObserver>(
+    this: *mut cef_dev_tools_message_observer_t,
+    browser: *mut cef_browser_t,
+) {
+    let obj: &RcImpl<_, I> = RcImpl::get(this);
+    obj.interface
+        .on_dev_tools_agent_attached(CefBrowser::from(browser));
+}
+
+unsafe extern "C" fn on_dev_tools_agent_detached<I: CefDevToolsMessageObserver>(
+    this: *mut cef_dev_tools_message_observer_t,
+    browser: *mut cef_browser_t,
+) {
+    let obj: &RcImpl<_, I> = RcImpl::get(this);
+    obj.interface
+        .on_dev_tools_agent_detached(CefBrowser::from(browser));
+}
+
+type MethodCallback = Box<dyn FnOnce(Result<Value>) + Send>;
+type EventListener = Arc<dyn Fn(&Value) + Send + Sync>;
+
+/// Id of the next message sent by a [DevToolsClient].
+///
+/// Shared by all clients, since CEF passes the results of a browser to all of its observers.
+static NEXT_MESSAGE_ID: AtomicI32 = AtomicI32::new(1);
+
+#[derive(Default)]
+struct DevToolsState {
+    pending: Mutex<HashMap<i32, MethodCallback>>,
+    listeners: Mutex<HashMap<String, Vec<EventListener>>>,
+}
+
+impl DevToolsState {
+    fn fail_pending(&self) {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+        for (_, callback) in pending {
+            callback(Err(Error::DevToolsDetached));
+        }
+    }
+}
+
+struct DevToolsObserver(Arc<DevToolsState>);
+
+impl CefDevToolsMessageObserver for DevToolsObserver {
+    fn on_dev_tools_method_result(
+        &self,
+        _browser: CefBrowser,
+        message_id: i32,
+        success: bool,
+        result: &[u8],
+    ) {
+        let Some(callback) = self.0.pending.lock().unwrap().remove(&message_id) else {
+            return;
+        };
+        let result = serde_json::from_slice::<Value>(result).map_err(Error::from);
+        callback(result.and_then(|value| {
+            if success {
+                return Ok(value);
+            }
+            Err(Error::DevToolsMethod {
+                code: value["code"].as_i64().unwrap_or_default(),
+                message: value["message"].as_str().unwrap_or_default().to_string(),
+            })
+        }));
+    }
+
+    fn on_dev_tools_event(&self, _browser: CefBrowser, method: CefString, params: &[u8]) {
+        let method = method.to_string();
+        let listeners = self
+            .0
+            .listeners
+            .lock()
+            .unwrap()
+            .get(&method)
+            .cloned()
+            .unwrap_or_default();
+        if listeners.is_empty() {
+            return;
+        }
+        let params = serde_json::from_slice::<Value>(params).unwrap_or(Value::Null);
+        for listener in listeners {
+            listener(&params);
+        }
+    }
+
+    fn on_dev_tools_agent_detached(&self, _browser: CefBrowser) {
+        self.0.fail_pending();
+    }
+}
+
+/// A DevTools protocol client bound to a browser.
+///
+/// Method calls are sent with [CefBrowserHost::send_dev_tools_message] and their results are
+/// matched by message id. The observer is unregistered when the client is dropped, and calls
+/// still pending at that point fail with [Error::DevToolsDetached].
+///
+/// All methods must be called on the browser process UI thread.
+pub struct DevToolsClient {
+    host: CefBrowserHost,
+    state: Arc<DevToolsState>,
+    _registration: CefRegistration,
+}
+
+impl DevToolsClient {
+    pub fn new(host: CefBrowserHost) -> Result<Self> {
+        let state = Arc::new(DevToolsState::default());
+        let registration = host
+            .add_dev_tools_message_observer(DevToolsObserver(state.clone()))
+            .ok_or(Error::NullPtr)?;
+        Ok(Self {
+            host,
+            state,
+            _registration: registration,
+        })
+    }
+
+    /// Execute `method` with `params` and call `callback` with its result.
+    ///
+    /// Returns the message id assigned to the call, unique in the process.
+    pub fn execute_with<F>(&self, method: &str, params: Value, callback: F) -> Result<i32>
+    where
+        F: FnOnce(Result<Value>) + Send + 'static,
+    {
+        let id = NEXT_MESSAGE_ID.fetch_add(1, Ordering::Relaxed);
+        let message = serde_json::json!({
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+        let message = serde_json::to_vec(&message)?;
+
+        self.state
+            .pending
+            .lock()
+            .unwrap()
+            .insert(id, Box::new(callback));
+        if !self
+            .host
+            .send_dev_tools_message(&message)
+            .unwrap_or_default()
+        {
+            self.state.pending.lock().unwrap().remove(&id);
+            return Err(Error::CannotSendDevToolsMessage);
+        }
+
+        Ok(id)
+    }
+
+    /// Execute `method` with `params`. The returned future resolves with the `result` object of
+    /// the response.
+    pub fn execute(&self, method: &str, params: Value) -> DevToolsResponse {
+        let (sender, receiver) = oneshot::channel();
+        let sent = self.execute_with(method, params, move |result| sender.send(result));
+        match sent {
+            Ok(_) => DevToolsResponse(receiver),
+            Err(e) => DevToolsResponse::ready(Err(e)),
+        }
+    }
+
+    /// Call `listener` with the `params` of every `event`, e.g. `Network.responseReceived`.
+    ///
+    /// Events are only emitted for domains that are enabled, e.g. with `Network.enable`.
+    pub fn subscribe<F>(&self, event: &str, listener: F)
+    where
+        F: Fn(&Value) + Send + Sync + 'static,
+    {
+        self.state
+            .listeners
+            .lock()
+            .unwrap()
+            .entry(event.to_string())
+            .or_default()
+            .push(Arc::new(listener));
+    }
+
+    /// Remove all listeners of `event`.
+    pub fn unsubscribe(&self, event: &str) {
+        self.state.listeners.lock().unwrap().remove(event);
+    }
+}
+
+impl Drop for DevToolsClient {
+    fn drop(&mut self) {
+        self.state.fail_pending();
+    }
+}
+
+/// The future returned by [DevToolsClient::execute].
+pub struct DevToolsResponse(oneshot::Receiver<Result<Value>>);
+
+#[cfg_attr(not(feature = "devtools-protocol"), allow(dead_code))]
+impl DevToolsResponse {
+    fn ready(result: Result<Value>) -> Self {
+        Self(oneshot::Receiver::ready(result))
+    }
+}
+
+impl Future for DevToolsResponse {
+    type Output = Result<Value>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
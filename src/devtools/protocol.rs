@@ -0,0 +1,101 @@
+//! Typed DevTools protocol domains
+//!
+//! The domains are generated from `browser_protocol.json` and `js_protocol.json` at build time.
+//! Set `CEF_DEVTOOLS_PROTOCOL_PATH` to the directory containing them when the
+//! `devtools-protocol` feature is enabled. Without it the build only warns and no domains are
+//! generated.
+//!
+//! Each domain is a module with its types, the parameters and returns of its commands and the
+//! parameters of its events. [DevToolsClient] has an accessor for each domain:
+//!
+//! ```no_run
+//! # async fn example(client: cef::DevToolsClient) -> cef::Result<()> {
+//! use cef::protocol::page::CaptureScreenshotParams;
+//!
+//! let page = client.page();
+//! page.enable().await?;
+//! let screenshot = page
+//!     .capture_screenshot(CaptureScreenshotParams::default())
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{DevToolsClient, DevToolsResponse};
+use crate::error::Result;
+
+/// The parameters of a DevTools protocol method.
+pub trait Command: Serialize {
+    /// The method name, e.g. `Page.captureScreenshot`.
+    const METHOD: &'static str;
+    /// The `result` object of the response.
+    type Returns: DeserializeOwned;
+}
+
+/// The parameters of a DevTools protocol event.
+pub trait Event: DeserializeOwned {
+    /// The event name, e.g. `Page.loadEventFired`.
+    const NAME: &'static str;
+}
+
+/// The future returned by [DevToolsClient::send].
+pub struct CdpResponse<T> {
+    inner: DevToolsResponse,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> Future for CdpResponse<T> {
+    type Output = Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.inner)
+            .poll(cx)
+            .map(|result| Ok(serde_json::from_value(result?)?))
+    }
+}
+
+impl DevToolsClient {
+    /// Execute the method of `command`.
+    pub fn send<C: Command>(&self, command: C) -> CdpResponse<C::Returns> {
+        let inner = match serde_json::to_value(&command) {
+            Ok(params) => self.execute(C::METHOD, params),
+            Err(e) => DevToolsResponse::ready(Err(e.into())),
+        };
+        CdpResponse {
+            inner,
+            marker: PhantomData,
+        }
+    }
+
+    /// Call `listener` on every `E` event. Events that fail to deserialize are ignored.
+    pub fn on<E, F>(&self, listener: F)
+    where
+        E: Event,
+        F: Fn(E) + Send + Sync + 'static,
+    {
+        self.subscribe(E::NAME, move |params| {
+            if let Ok(event) = E::deserialize(params) {
+                listener(event);
+            }
+        });
+    }
+}
+
+#[allow(nonstandard_style, unused, clippy::all, rustdoc::all, rustfmt::skip)]
+mod generated {
+    use super::{CdpResponse, Command, DevToolsClient, Event};
+
+    include!(concat!(env!("OUT_DIR"), "/devtools_protocol.rs"));
+}
+// Empty when the build found no schemas.
+#[allow(unused_imports)]
+pub use generated::*;
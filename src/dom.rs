@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+
+use crate::{prelude::*, CefDomDocumentType, CefDomFormControlType, CefDomNodeType, CefRect};
+
+/// See [cef_domdocument_t] for more documentation.
+///
+/// DOM objects are only valid on the render process main thread, inside the
+/// callback that handed them out.
+#[derive(Debug, Clone)]
+#[wrapper]
+pub struct CefDomDocument(cef_domdocument_t);
+
+impl CefDomDocument {
+    wrapper_methods!(
+        /// See [cef_domdocument_t::get_type]
+        fn get_type(&self) -> CefDomDocumentType;
+
+        /// See [cef_domdocument_t::get_document]
+        fn get_document(&self) -> CefDomNode {
+            get_document.and_then(|f| unsafe { CefDomNode::from_nullable(f(self.get_this())) })
+        }
+
+        /// See [cef_domdocument_t::get_body]
+        fn get_body(&self) -> CefDomNode {
+            get_body.and_then(|f| unsafe { CefDomNode::from_nullable(f(self.get_this())) })
+        }
+
+        /// See [cef_domdocument_t::get_head]
+        fn get_head(&self) -> CefDomNode {
+            get_head.and_then(|f| unsafe { CefDomNode::from_nullable(f(self.get_this())) })
+        }
+
+        /// See [cef_domdocument_t::get_title]
+        fn get_title(&self) -> CefString {
+            get_title.and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_domdocument_t::get_element_by_id]
+        fn get_element_by_id(&self, id: &str) -> CefDomNode {
+            get_element_by_id.and_then(|f| unsafe {
+                CefDomNode::from_nullable(f(self.get_this(), &CefString::from(id).as_raw()))
+            })
+        }
+
+        /// See [cef_domdocument_t::get_focused_node]
+        fn get_focused_node(&self) -> CefDomNode {
+            get_focused_node.and_then(|f| unsafe { CefDomNode::from_nullable(f(self.get_this())) })
+        }
+
+        /// See [cef_domdocument_t::has_selection]
+        fn has_selection(&self) -> bool;
+
+        /// See [cef_domdocument_t::get_selection_start_offset]
+        fn get_selection_start_offset(&self) -> i32;
+
+        /// See [cef_domdocument_t::get_selection_end_offset]
+        fn get_selection_end_offset(&self) -> i32;
+
+        /// See [cef_domdocument_t::get_selection_as_markup]
+        fn get_selection_as_markup(&self) -> CefString {
+            get_selection_as_markup
+                .and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_domdocument_t::get_selection_as_text]
+        fn get_selection_as_text(&self) -> CefString {
+            get_selection_as_text
+                .and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_domdocument_t::get_base_url]
+        fn get_base_url(&self) -> CefString {
+            get_base_url.and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_domdocument_t::get_complete_url]
+        fn get_complete_url(&self, partial_url: &str) -> CefString {
+            get_complete_url.and_then(|f| unsafe {
+                CefString::from_userfree_cef(f(
+                    self.get_this(),
+                    &CefString::from(partial_url).as_raw(),
+                ))
+            })
+        }
+    );
+}
+
+/// See [cef_domnode_t] for more documentation.
+///
+/// DOM objects are only valid on the render process main thread, inside the
+/// callback that handed them out.
+#[derive(Debug, Clone)]
+#[wrapper]
+pub struct CefDomNode(cef_domnode_t);
+
+impl CefDomNode {
+    unsafe fn from_nullable(ptr: *mut cef_domnode_t) -> Option<Self> {
+        if ptr.is_null() {
+            None
+        } else {
+            Some(Self::from(ptr))
+        }
+    }
+
+    wrapper_methods!(
+        /// See [cef_domnode_t::get_type]
+        fn get_type(&self) -> CefDomNodeType;
+
+        /// See [cef_domnode_t::is_text]
+        fn is_text(&self) -> bool;
+
+        /// See [cef_domnode_t::is_element]
+        fn is_element(&self) -> bool;
+
+        /// See [cef_domnode_t::is_editable]
+        fn is_editable(&self) -> bool;
+
+        /// See [cef_domnode_t::is_form_control_element]
+        fn is_form_control_element(&self) -> bool;
+
+        /// See [cef_domnode_t::get_form_control_element_type]
+        fn get_form_control_element_type(&self) -> CefDomFormControlType;
+
+        /// See [cef_domnode_t::is_same]
+        fn is_same(&self, that: CefDomNode) -> bool;
+
+        /// See [cef_domnode_t::get_name]
+        fn get_name(&self) -> CefString {
+            get_name.and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_domnode_t::get_value]
+        fn get_value(&self) -> CefString {
+            get_value.and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_domnode_t::set_value]
+        fn set_value(&self, value: &str) -> bool {
+            set_value.map(|f| unsafe { f(self.get_this(), &CefString::from(value).as_raw()) == 1 })
+        }
+
+        /// See [cef_domnode_t::get_as_markup]
+        fn get_as_markup(&self) -> CefString {
+            get_as_markup.and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_domnode_t::get_document]
+        fn get_document(&self) -> CefDomDocument {
+            get_document.and_then(|f| unsafe {
+                let document = f(self.get_this());
+                if document.is_null() {
+                    None
+                } else {
+                    Some(CefDomDocument::from(document))
+                }
+            })
+        }
+
+        /// See [cef_domnode_t::get_parent]
+        fn get_parent(&self) -> CefDomNode {
+            get_parent.and_then(|f| unsafe { Self::from_nullable(f(self.get_this())) })
+        }
+
+        /// See [cef_domnode_t::get_previous_sibling]
+        fn get_previous_sibling(&self) -> CefDomNode {
+            get_previous_sibling.and_then(|f| unsafe { Self::from_nullable(f(self.get_this())) })
+        }
+
+        /// See [cef_domnode_t::get_next_sibling]
+        fn get_next_sibling(&self) -> CefDomNode {
+            get_next_sibling.and_then(|f| unsafe { Self::from_nullable(f(self.get_this())) })
+        }
+
+        /// See [cef_domnode_t::has_children]
+        fn has_children(&self) -> bool;
+
+        /// See [cef_domnode_t::get_first_child]
+        fn get_first_child(&self) -> CefDomNode {
+            get_first_child.and_then(|f| unsafe { Self::from_nullable(f(self.get_this())) })
+        }
+
+        /// See [cef_domnode_t::get_last_child]
+        fn get_last_child(&self) -> CefDomNode {
+            get_last_child.and_then(|f| unsafe { Self::from_nullable(f(self.get_this())) })
+        }
+
+        /// See [cef_domnode_t::get_element_tag_name]
+        fn get_element_tag_name(&self) -> CefString {
+            get_element_tag_name
+                .and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_domnode_t::has_element_attributes]
+        fn has_element_attributes(&self) -> bool;
+
+        /// See [cef_domnode_t::has_element_attribute]
+        fn has_element_attribute(&self, attr_name: &str) -> bool {
+            has_element_attribute
+                .map(|f| unsafe { f(self.get_this(), &CefString::from(attr_name).as_raw()) == 1 })
+        }
+
+        /// See [cef_domnode_t::get_element_attribute]
+        fn get_element_attribute(&self, attr_name: &str) -> CefString {
+            get_element_attribute.and_then(|f| unsafe {
+                CefString::from_userfree_cef(f(
+                    self.get_this(),
+                    &CefString::from(attr_name).as_raw(),
+                ))
+            })
+        }
+
+        /// See [cef_domnode_t::get_element_attributes]
+        fn get_element_attributes(&self) -> HashMap<String, String> {
+            get_element_attributes.map(|f| unsafe {
+                let map = cef_string_map_alloc();
+                f(self.get_this(), map);
+                let attributes = crate::string::parse_string_map(map);
+                cef_string_map_free(map);
+                attributes
+            })
+        }
+
+        /// See [cef_domnode_t::set_element_attribute]
+        fn set_element_attribute(&self, attr_name: &str, value: &str) -> bool {
+            set_element_attribute.map(|f| unsafe {
+                f(
+                    self.get_this(),
+                    &CefString::from(attr_name).as_raw(),
+                    &CefString::from(value).as_raw(),
+                ) == 1
+            })
+        }
+
+        /// See [cef_domnode_t::get_element_inner_text]
+        fn get_element_inner_text(&self) -> CefString {
+            get_element_inner_text
+                .and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_domnode_t::get_element_bounds]
+        fn get_element_bounds(&self) -> CefRect;
+    );
+
+    /// Collect the direct children of this node, in document order.
+    pub fn children(&self) -> Vec<CefDomNode> {
+        let mut children = Vec::new();
+        let mut child = self.get_first_child();
+        while let Some(node) = child {
+            child = node.get_next_sibling();
+            children.push(node);
+        }
+        children
+    }
+}
+
+/// See [cef_domvisitor_t]
+pub trait CefDomVisitor: Sized {
+    /// See [cef_domvisitor_t::visit]
+    fn visit(&self, document: CefDomDocument);
+
+    #[doc(hidden)]
+    fn into_raw(self) -> *mut cef_domvisitor_t {
+        unsafe extern "C" fn visit<T: CefDomVisitor>(
+            self_: *mut _cef_domvisitor_t,
+            document: *mut _cef_domdocument_t,
+        ) {
+            let object: &crate::rc::RcImpl<_, T> = crate::rc::RcImpl::get(self_);
+            object.interface.visit(CefDomDocument::from(document));
+        }
+
+        let mut object: cef_domvisitor_t = unsafe { std::mem::zeroed() };
+        object.visit = Some(visit::<Self>);
+        crate::rc::RcImpl::new(object, self).cast()
+    }
+}
@@ -18,6 +18,50 @@ pub enum Error {
     IgnoreJsFn,
     #[error("cannot post to cef_thread({0})")]
     CannotPostTask(/*cef_thread_id*/ u8),
+    #[error("not called on cef_thread({0})")]
+    WrongThread(/*cef_thread_id*/ u8),
+    #[error("cannot send devtools message")]
+    CannotSendDevToolsMessage,
+    #[error("devtools method failed({code}): {message}")]
+    DevToolsMethod { code: i64, message: String },
+    #[error("devtools agent detached")]
+    DevToolsDetached,
+    #[error("url request {status:?}: {error:?}")]
+    UrlRequest {
+        status: cef_sys::cef_urlrequest_status_t,
+        error: cef_sys::cef_errorcode_t,
+    },
+    #[error("cannot enter v8 context")]
+    CannotEnterV8Context,
+    #[error("`{0}` is not a JS object")]
+    NotV8Object(String),
+    #[error("not a JS function")]
+    NotV8Function,
+    #[error("js exception: {0}")]
+    V8Exception(#[from] crate::v8::V8Exception),
+    #[error("not a JS ArrayBuffer")]
+    NotV8ArrayBuffer,
+    #[error("ArrayBuffer is already in use")]
+    V8ArrayBufferInUse,
+    #[error("ArrayBuffer of {0} bytes is too small")]
+    V8ArrayBufferTooSmall(usize),
+    #[error("cannot register v8 extension `{0}`")]
+    CannotRegisterV8Extension(String),
+    #[cfg(feature = "serde")]
+    #[error("v8 serde: {0}")]
+    V8Serde(String),
+    #[error("unknown ipc message `{0}`")]
+    UnknownIpcMessage(String),
+    #[error("malformed ipc message `{name}`: {error}")]
+    MalformedIpcMessage { name: String, error: String },
+    #[error("rpc call timed out")]
+    RpcTimeout,
+    #[error("rpc call cancelled")]
+    RpcCancelled,
+    #[error("rpc call failed: {0}")]
+    RpcFailed(String),
+    #[error("json: {0}")]
+    Json(#[from] serde_json::Error),
     #[error("raw: {0:?}")]
     Raw(Option<crate::string::CefString>),
 }
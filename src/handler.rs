@@ -37,6 +37,9 @@ pub use keyboard::*;
 mod life_span;
 pub use life_span::*;
 
+mod lifecycle;
+pub(crate) use lifecycle::*;
+
 mod load;
 pub use load::*;
 
@@ -1,4 +1,5 @@
 use std::ops::Deref;
+use std::ptr::null_mut;
 use std::sync::Arc;
 
 use crate::{
@@ -103,21 +104,26 @@ impl<T: CefLifeSpanHandler> CefLifeSpanWrapper<T> {
     ) {
         let object: &crate::rc::RcImpl<_, Self> = crate::rc::RcImpl::get(self_);
         let browser = crate::CefBrowser::from(browser);
-        let window_info = &mut *window_info;
-        let client = &mut *client;
-        let settings = &mut *settings;
-        let extra_info = &mut *extra_info;
+        let mut info = CefWindowInfo::from_raw(window_info).unwrap_or_default();
+        let mut client_value = (!(*client).is_null()).then(|| crate::CefAnyClient::from(*client));
+        let mut browser_settings = CefBrowserSettings::from_raw(*settings);
+        let mut extra = (!(*extra_info).is_null()).then(|| CefDictionaryValue::from(*extra_info));
         let mut use_default = *use_default_window != 0;
 
         object.interface.on_before_dev_tools_popup(
             browser,
-            window_info,
-            client,
-            settings,
-            extra_info,
+            &mut info,
+            &mut client_value,
+            &mut browser_settings,
+            &mut extra,
             &mut use_default,
         );
 
+        // The client and extra info references are handed back to CEF.
+        *client = client_value.map(|c| c.into_raw()).unwrap_or(null_mut());
+        *extra_info = extra.map(|e| e.into_raw()).unwrap_or(null_mut());
+        write_window_info(&info, &mut *window_info);
+        write_browser_settings(&browser_settings, &mut *settings);
         *use_default_window = if use_default { 1 } else { 0 };
     }
 
@@ -150,6 +156,8 @@ impl<T: CefLifeSpanHandler> CefLifeSpanWrapper<T> {
     ) {
         let object: &crate::rc::RcImpl<_, Self> = crate::rc::RcImpl::get(self_);
         let browser = crate::CefBrowser::from(browser);
+        #[cfg(feature = "serde")]
+        crate::cancel_rpc_calls_for_browser(&browser);
         object.interface.on_before_close(browser);
     }
 
@@ -187,13 +195,17 @@ pub trait CefLifeSpanHandler: Sized {
     }
 
     /// See [cef_life_span_handler_t::on_before_dev_tools_popup].
+    ///
+    /// For browsers hosted in a [crate::CefBrowserView], leave `use_default_window` as `false`
+    /// and handle [crate::BrowserViewDelegate::on_popup_browser_view_created] to dock DevTools
+    /// into the existing window. Changes to the other arguments are passed back to CEF.
     fn on_before_dev_tools_popup(
         &self,
         browser: crate::CefBrowser,
-        window_info: &mut _cef_window_info_t,
-        client: &mut *mut _cef_client_t,
-        settings: &mut _cef_browser_settings_t,
-        extra_info: &mut *mut _cef_dictionary_value_t,
+        window_info: &mut CefWindowInfo,
+        client: &mut Option<crate::CefAnyClient>,
+        settings: &mut CefBrowserSettings,
+        extra_info: &mut Option<CefDictionaryValue>,
         use_default_window: &mut bool,
     ) {
     }
@@ -210,6 +222,57 @@ pub trait CefLifeSpanHandler: Sized {
     fn on_before_close(&self, browser: crate::CefBrowser) {}
 }
 
+/// Copy `value` into a string owned by CEF.
+unsafe fn set_string(value: Option<&CefString>, out: &mut cef_string_t) {
+    match value {
+        Some(value) => {
+            let raw = value.as_raw();
+            cef_string_utf16_set(raw.str_, raw.length, out, 1);
+        }
+        None => cef_string_utf16_clear(out),
+    }
+}
+
+/// Write `info` back into the out-param. `as_raw` only borrows the strings, so they are copied.
+unsafe fn write_window_info(info: &CefWindowInfo, raw: &mut cef_window_info_t) {
+    let mut new = info.as_raw();
+    new.window_name = raw.window_name;
+    *raw = new;
+    set_string(Some(&info.window_name), &mut raw.window_name);
+}
+
+/// Write `settings` back into the out-param. `as_raw` only borrows the strings, so they are copied.
+unsafe fn write_browser_settings(settings: &CefBrowserSettings, raw: &mut cef_browser_settings_t) {
+    let mut new = settings.clone().as_raw();
+    new.standard_font_family = raw.standard_font_family;
+    new.fixed_font_family = raw.fixed_font_family;
+    new.serif_font_family = raw.serif_font_family;
+    new.sans_serif_font_family = raw.sans_serif_font_family;
+    new.cursive_font_family = raw.cursive_font_family;
+    new.fantasy_font_family = raw.fantasy_font_family;
+    new.default_encoding = raw.default_encoding;
+    *raw = new;
+
+    let strings = [
+        (
+            &settings.standard_font_family,
+            &mut raw.standard_font_family,
+        ),
+        (&settings.fixed_font_family, &mut raw.fixed_font_family),
+        (&settings.serif_font_family, &mut raw.serif_font_family),
+        (
+            &settings.sans_serif_font_family,
+            &mut raw.sans_serif_font_family,
+        ),
+        (&settings.cursive_font_family, &mut raw.cursive_font_family),
+        (&settings.fantasy_font_family, &mut raw.fantasy_font_family),
+        (&settings.default_encoding, &mut raw.default_encoding),
+    ];
+    for (value, out) in strings {
+        set_string(value.as_ref(), out);
+    }
+}
+
 /*
 impl CefLifeSpanHandler for () {
     fn into_raw(self) -> *mut cef_life_span_handler_t {
@@ -0,0 +1,65 @@
+//! Request and frame handlers of every [CefClient](crate::CefClient)
+//!
+//! These slots of [cef_client_t] are reserved: [CefClient](crate::CefClient) doesn't expose them,
+//! so the pending calls and queries they cancel can't outlive their frame. Callbacks beyond the
+//! ones below keep CEF's default behavior.
+
+use crate::{prelude::*, CefBrowser, CefFrame};
+
+/// Request handler of every [CefClient](crate::CefClient), cancelling the pending calls and
+/// message router queries of browsers whose renderer process terminates, and the queries of
+/// frames that navigate.
+pub(crate) fn request_handler() -> *mut cef_request_handler_t {
+    unsafe extern "C" fn on_before_browse(
+        _self_: *mut _cef_request_handler_t,
+        browser: *mut _cef_browser_t,
+        frame: *mut _cef_frame_t,
+        request: *mut _cef_request_t,
+        _user_gesture: ::std::os::raw::c_int,
+        _is_redirect: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int {
+        let browser = CefBrowser::from(browser);
+        let frame = CefFrame::from(frame);
+        let _request = crate::CefRequest::from(request);
+        crate::message_router::cancel_router_queries_for_frame(&browser, &frame);
+        false as _
+    }
+
+    unsafe extern "C" fn on_render_process_terminated(
+        _self_: *mut _cef_request_handler_t,
+        browser: *mut _cef_browser_t,
+        _status: cef_termination_status_t,
+        _error_code: ::std::os::raw::c_int,
+        _error_string: *const cef_string_t,
+    ) {
+        let browser = CefBrowser::from(browser);
+        #[cfg(feature = "serde")]
+        crate::cancel_rpc_calls_for_browser(&browser);
+        crate::message_router::cancel_router_queries_for_browser(&browser);
+    }
+
+    let mut object: cef_request_handler_t = unsafe { std::mem::zeroed() };
+    object.on_before_browse = Some(on_before_browse);
+    object.on_render_process_terminated = Some(on_render_process_terminated);
+    crate::rc::RcImpl::new(object, ()).cast()
+}
+
+/// Frame handler of every [CefClient](crate::CefClient), cancelling the pending calls and
+/// message router queries of detached frames.
+pub(crate) fn frame_handler() -> *mut cef_frame_handler_t {
+    unsafe extern "C" fn on_frame_detached(
+        _self_: *mut _cef_frame_handler_t,
+        browser: *mut _cef_browser_t,
+        frame: *mut _cef_frame_t,
+    ) {
+        let browser = CefBrowser::from(browser);
+        let frame = CefFrame::from(frame);
+        #[cfg(feature = "serde")]
+        crate::cancel_rpc_calls_for_frame(&frame);
+        crate::message_router::cancel_router_queries_for_frame(&browser, &frame);
+    }
+
+    let mut object: cef_frame_handler_t = unsafe { std::mem::zeroed() };
+    object.on_frame_detached = Some(on_frame_detached);
+    crate::rc::RcImpl::new(object, ()).cast()
+}
@@ -5,26 +5,36 @@ mod args;
 mod browser;
 mod client;
 mod command_line;
+mod devtools;
+mod dom;
 mod error;
 mod handler;
 mod image;
 mod interface;
 mod menu_model;
+mod message_router;
 mod multimap;
 mod net;
+mod oneshot;
 mod preference_manager;
 mod prelude;
 mod process_message;
 mod rc;
+mod registration;
 //mod sandbox;
 mod scoped;
 mod settings;
 mod string;
+mod string_visitor;
 mod task;
 mod v8;
 mod value;
 mod view;
 
+// Lets the tests use the macros, which refer to this crate as `::cef`.
+#[cfg(test)]
+extern crate self as cef;
+
 use std::{
     marker::PhantomData,
     ops::{Deref, DerefMut},
@@ -36,16 +46,24 @@ pub use args::*;
 pub use browser::*;
 pub use cef_sys as sys;
 use cef_sys::cef_base_ref_counted_t;
+pub use cef_wrapper_macro::js_function;
+#[cfg(feature = "serde")]
+pub use cef_wrapper_macro::{command, IpcMessage};
 pub use client::*;
 pub use command_line::*;
+pub use devtools::*;
+pub use dom::*;
 pub use error::*;
 pub use handler::*;
 pub use image::*;
 pub use menu_model::*;
+pub use message_router::*;
 pub use net::*;
-pub use process_message::CefProcessMessage;
+pub use process_message::*;
+pub use registration::*;
 pub use settings::*;
 pub use string::CefString;
+pub use string_visitor::*;
 pub use task::*;
 pub use v8::*;
 pub use value::*;
@@ -108,6 +126,12 @@ mod alias {
     pub type CefErrorCode = cef_sys::cef_errorcode_t;
 
     pub type CefTransitionType = cef_sys::cef_transition_type_t;
+
+    pub type CefDomDocumentType = cef_sys::cef_dom_document_type_t;
+
+    pub type CefDomNodeType = cef_sys::cef_dom_node_type_t;
+
+    pub type CefDomFormControlType = cef_sys::cef_dom_form_control_type_t;
 }
 pub use alias::*;
 
@@ -0,0 +1,111 @@
+//! JS queries answered by the browser process
+//!
+//! A port of the message router of the CEF C++ wrapper. [MessageRouterRendererSide] installs
+//! two functions on the global object of every context:
+//!
+//! ```js
+//! const id = window.cefQuery({
+//!     request: "my_request",
+//!     persistent: false,
+//!     onSuccess: (response) => {},
+//!     onFailure: (error_code, error_message) => {},
+//! });
+//! window.cefQueryCancel(id);
+//! ```
+//!
+//! Queries are sent to the [MessageRouterBrowserSide], which passes them to its
+//! [MessageRouterHandler]s in order until one handles them. The handler answers through a
+//! [MessageRouterCallback], once, or for persistent queries any number of times until it fails.
+//! A query no handler accepts fails with error code `-1`.
+//!
+//! Both halves are driven from the handlers of the app, and must use the same
+//! [MessageRouterConfig]. Pending queries are cancelled when their context is released in the
+//! renderer process, and when their browser closes in the browser process. The handlers of
+//! every [CefClient](crate::CefClient) also cancel them when their frame navigates or is
+//! detached, or their renderer process terminates.
+//!
+//! ```no_run
+//! use cef::{
+//!     CefBrowser, CefFrame, CefProcessMessage, CefV8Context, MessageRouterBrowserSide,
+//!     MessageRouterCallback, MessageRouterConfig, MessageRouterHandler, MessageRouterRendererSide,
+//! };
+//!
+//! struct Echo;
+//!
+//! impl MessageRouterHandler for Echo {
+//!     fn on_query(
+//!         &self,
+//!         _browser: CefBrowser,
+//!         _frame: CefFrame,
+//!         _query_id: i64,
+//!         request: &str,
+//!         _persistent: bool,
+//!         callback: MessageRouterCallback,
+//!     ) -> bool {
+//!         _ = callback.success(request);
+//!         true
+//!     }
+//! }
+//!
+//! // Browser process, from `CefClient::on_process_message_received`
+//! fn browser_message(
+//!     router: &MessageRouterBrowserSide,
+//!     browser: CefBrowser,
+//!     frame: CefFrame,
+//!     message: CefProcessMessage,
+//! ) -> bool {
+//!     router.on_process_message_received(browser, frame, &message)
+//! }
+//!
+//! fn browser_side() -> MessageRouterBrowserSide {
+//!     MessageRouterBrowserSide::new(MessageRouterConfig::default()).with_handler(Echo)
+//! }
+//!
+//! // Renderer process, from `CefRenderProcessHandler::on_context_created`
+//! fn context_created(router: &MessageRouterRendererSide, context: CefV8Context) {
+//!     _ = router.on_context_created(&context);
+//! }
+//!
+//! fn renderer_side() -> MessageRouterRendererSide {
+//!     MessageRouterRendererSide::new(MessageRouterConfig::default())
+//! }
+//! ```
+
+mod browser;
+mod renderer;
+
+pub use browser::*;
+pub use renderer::*;
+
+/// Error code of the queries no handler accepted.
+const UNHANDLED_ERROR_CODE: i32 = -1;
+
+/// Names of the JS functions of a message router, which must be the same in both processes.
+#[derive(Debug, Clone)]
+pub struct MessageRouterConfig {
+    /// Name of the global query function, `cefQuery` by default.
+    pub js_query_function: String,
+    /// Name of the global cancel function, `cefQueryCancel` by default.
+    pub js_cancel_function: String,
+}
+
+impl Default for MessageRouterConfig {
+    fn default() -> Self {
+        Self {
+            js_query_function: "cefQuery".to_string(),
+            js_cancel_function: "cefQueryCancel".to_string(),
+        }
+    }
+}
+
+impl MessageRouterConfig {
+    /// Name of the process messages carrying queries and their responses.
+    fn query_message_name(&self) -> String {
+        format!("{}Msg", self.js_query_function)
+    }
+
+    /// Name of the process messages cancelling queries.
+    fn cancel_message_name(&self) -> String {
+        format!("{}Msg", self.js_cancel_function)
+    }
+}
@@ -0,0 +1,306 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc, Mutex, Weak,
+    },
+};
+
+use super::{MessageRouterConfig, UNHANDLED_ERROR_CODE};
+use crate::{prelude::*, CefBrowser, CefFrame, CefProcessId, CefProcessMessage};
+
+/// Handles the queries of a [MessageRouterBrowserSide], see the [module docs](crate::MessageRouterConfig).
+pub trait MessageRouterHandler: Send + Sync + 'static {
+    /// Called on the UI thread with a new query, `query_id` being unique in the browser process.
+    ///
+    /// Return true to handle the query, answering it through `callback` now or later, or false
+    /// to pass it to the next handler.
+    fn on_query(
+        &self,
+        browser: CefBrowser,
+        frame: CefFrame,
+        query_id: i64,
+        request: &str,
+        persistent: bool,
+        callback: MessageRouterCallback,
+    ) -> bool;
+
+    /// Called on the UI thread when a query handled by this handler is cancelled, by JS or
+    /// because its frame or browser went away. Its callback does nothing from now on.
+    fn on_query_canceled(&self, _browser: CefBrowser, _frame: CefFrame, _query_id: i64) {}
+}
+
+struct PendingQuery {
+    browser: CefBrowser,
+    frame: CefFrame,
+    browser_id: i32,
+    frame_id: String,
+    request_id: i32,
+    persistent: bool,
+    /// The handler which accepted the query, none while handlers are being asked.
+    handler: Option<Arc<dyn MessageRouterHandler>>,
+}
+
+type QueryMap = Mutex<BTreeMap<i64, PendingQuery>>;
+
+type PendingQueries = Arc<QueryMap>;
+
+static NEXT_QUERY_ID: AtomicI64 = AtomicI64::new(1);
+
+/// Pending queries of every live [MessageRouterBrowserSide], cancelled by the handlers of every
+/// [CefClient](crate::CefClient).
+static ROUTERS: Mutex<Vec<Weak<QueryMap>>> = Mutex::new(Vec::new());
+
+fn frame_id(frame: &CefFrame) -> String {
+    frame.get_identifier().unwrap_or_default().to_string()
+}
+
+fn browser_id(browser: &CefBrowser) -> i32 {
+    browser.get_identifier().unwrap_or_default()
+}
+
+/// Browser process half of the message router, see the [module docs](crate::MessageRouterConfig).
+///
+/// Clones share their pending queries.
+#[derive(Clone)]
+pub struct MessageRouterBrowserSide {
+    config: Arc<MessageRouterConfig>,
+    handlers: Vec<Arc<dyn MessageRouterHandler>>,
+    pending: PendingQueries,
+}
+
+impl MessageRouterBrowserSide {
+    pub fn new(config: MessageRouterConfig) -> Self {
+        let pending = PendingQueries::default();
+        let mut routers = ROUTERS.lock().unwrap();
+        routers.retain(|router| router.strong_count() > 0);
+        routers.push(Arc::downgrade(&pending));
+        Self {
+            config: Arc::new(config),
+            handlers: Vec::new(),
+            pending,
+        }
+    }
+
+    /// Add `handler` after the previous ones.
+    pub fn with_handler(mut self, handler: impl MessageRouterHandler) -> Self {
+        self.handlers.push(Arc::new(handler));
+        self
+    }
+
+    /// Number of pending queries of `browser`, or of all browsers.
+    pub fn pending_count(&self, browser: Option<&CefBrowser>) -> usize {
+        let browser_id = browser.map(browser_id);
+        self.pending
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|query| browser_id.is_none_or(|id| query.browser_id == id))
+            .count()
+    }
+
+    /// Cancel the pending queries of `browser`, or of all browsers.
+    pub fn cancel_pending(&self, browser: Option<&CefBrowser>) {
+        let browser_id = browser.map(browser_id);
+        self.cancel(|query| browser_id.is_none_or(|id| query.browser_id == id));
+    }
+
+    /// Call from [CefLifeSpanHandler::on_before_close](crate::CefLifeSpanHandler::on_before_close).
+    pub fn on_before_close(&self, browser: &CefBrowser) {
+        self.cancel_pending(Some(browser));
+    }
+
+    /// Call from [CefClient::on_process_message_received](crate::CefClient::on_process_message_received).
+    ///
+    /// Returns false for messages not sent by a [MessageRouterRendererSide].
+    pub fn on_process_message_received(
+        &self,
+        browser: CefBrowser,
+        frame: CefFrame,
+        message: &CefProcessMessage,
+    ) -> bool {
+        let name = message.get_name().unwrap_or_default().to_string();
+        let Some(arguments) = message.get_argument_list() else {
+            return false;
+        };
+        if name == self.config.query_message_name() {
+            let request_id = arguments.get_int(0).unwrap_or_default();
+            let request = arguments.get_string(1).unwrap_or_default().to_string();
+            let persistent = arguments.get_bool(2).unwrap_or_default();
+            self.query_received(browser, frame, request_id, &request, persistent);
+            true
+        } else if name == self.config.cancel_message_name() {
+            let request_id = arguments.get_int(0).unwrap_or_default();
+            let browser_id = browser_id(&browser);
+            let frame_id = frame_id(&frame);
+            self.cancel(|query| {
+                query.browser_id == browser_id
+                    && query.frame_id == frame_id
+                    && query.request_id == request_id
+            });
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Ask the handlers in order to handle a new query, failing it if none does.
+    fn query_received(
+        &self,
+        browser: CefBrowser,
+        frame: CefFrame,
+        request_id: i32,
+        request: &str,
+        persistent: bool,
+    ) {
+        let query_id = NEXT_QUERY_ID.fetch_add(1, Ordering::Relaxed);
+        let query = PendingQuery {
+            browser: browser.clone(),
+            frame: frame.clone(),
+            browser_id: browser_id(&browser),
+            frame_id: frame_id(&frame),
+            request_id,
+            persistent,
+            handler: None,
+        };
+        // Registered first, so that handlers can answer synchronously.
+        self.pending.lock().unwrap().insert(query_id, query);
+        let callback = MessageRouterCallback {
+            config: self.config.clone(),
+            pending: self.pending.clone(),
+            query_id,
+        };
+        for handler in &self.handlers {
+            let handled = handler.on_query(
+                browser.clone(),
+                frame.clone(),
+                query_id,
+                request,
+                persistent,
+                callback.clone(),
+            );
+            if handled {
+                if let Some(query) = self.pending.lock().unwrap().get_mut(&query_id) {
+                    query.handler = Some(handler.clone());
+                }
+                return;
+            }
+        }
+        _ = callback.failure(UNHANDLED_ERROR_CODE, "unhandled query");
+    }
+
+    /// Remove the pending queries matching `cancel` and notify their handlers.
+    fn cancel(&self, cancel: impl Fn(&PendingQuery) -> bool) {
+        cancel_queries(&self.pending, cancel);
+    }
+}
+
+/// Remove the `pending` queries matching `cancel` and notify their handlers.
+fn cancel_queries(
+    pending: &Mutex<BTreeMap<i64, PendingQuery>>,
+    cancel: impl Fn(&PendingQuery) -> bool,
+) {
+    let cancelled: Vec<(i64, PendingQuery)> = {
+        let mut pending = pending.lock().unwrap();
+        let ids: Vec<i64> = pending
+            .iter()
+            .filter(|(_, query)| cancel(query))
+            .map(|(id, _)| *id)
+            .collect();
+        ids.into_iter()
+            .filter_map(|id| pending.remove(&id).map(|query| (id, query)))
+            .collect()
+    };
+    for (query_id, query) in cancelled {
+        if let Some(handler) = query.handler {
+            handler.on_query_canceled(query.browser, query.frame, query_id);
+        }
+    }
+}
+
+/// Cancel the queries matching `cancel` in every [MessageRouterBrowserSide].
+fn cancel_router_queries(cancel: impl Fn(&PendingQuery) -> bool) {
+    let routers: Vec<PendingQueries> = ROUTERS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(Weak::upgrade)
+        .collect();
+    for pending in routers {
+        cancel_queries(&pending, &cancel);
+    }
+}
+
+/// Cancel the queries of `browser`, when its renderer process terminates.
+pub(crate) fn cancel_router_queries_for_browser(browser: &CefBrowser) {
+    let browser_id = browser_id(browser);
+    cancel_router_queries(|query| query.browser_id == browser_id);
+}
+
+/// Cancel the queries of `frame`, when it's detached or navigates. Navigating the main frame
+/// cancels the queries of the whole browser.
+pub(crate) fn cancel_router_queries_for_frame(browser: &CefBrowser, frame: &CefFrame) {
+    if frame.is_main().unwrap_or_default() {
+        cancel_router_queries_for_browser(browser);
+    } else {
+        let browser_id = browser_id(browser);
+        let frame_id = frame_id(frame);
+        cancel_router_queries(|query| query.browser_id == browser_id && query.frame_id == frame_id);
+    }
+}
+
+/// Answers a query of a [MessageRouterHandler], from any thread.
+///
+/// Once the query completed or was cancelled, answering it does nothing.
+#[derive(Clone)]
+pub struct MessageRouterCallback {
+    config: Arc<MessageRouterConfig>,
+    pending: PendingQueries,
+    query_id: i64,
+}
+
+impl MessageRouterCallback {
+    /// Call the `onSuccess` JS callback with `response`, completing the query unless it's
+    /// persistent.
+    pub fn success(&self, response: &str) -> Result<()> {
+        let target = {
+            let mut pending = self.pending.lock().unwrap();
+            match pending.get(&self.query_id) {
+                Some(query) if query.persistent => Some((query.frame.clone(), query.request_id)),
+                Some(_) => pending
+                    .remove(&self.query_id)
+                    .map(|query| (query.frame, query.request_id)),
+                None => None,
+            }
+        };
+        let Some((frame, request_id)) = target else {
+            return Ok(());
+        };
+        let message = CefProcessMessage::create(&self.config.query_message_name())?;
+        let arguments = message.get_argument_list().ok_or(Error::NullPtr)?;
+        arguments.set_int(0, request_id);
+        arguments.set_bool(1, true);
+        arguments.set_string(2, CefString::from(response));
+        frame.send_process_message(CefProcessId::PID_RENDERER, message);
+        Ok(())
+    }
+
+    /// Call the `onFailure` JS callback with `error_code` and `error_message`, completing the
+    /// query.
+    pub fn failure(&self, error_code: i32, error_message: &str) -> Result<()> {
+        let query = self.pending.lock().unwrap().remove(&self.query_id);
+        let Some(query) = query else {
+            return Ok(());
+        };
+        let message = CefProcessMessage::create(&self.config.query_message_name())?;
+        let arguments = message.get_argument_list().ok_or(Error::NullPtr)?;
+        arguments.set_int(0, query.request_id);
+        arguments.set_bool(1, false);
+        arguments.set_int(2, error_code);
+        arguments.set_string(3, CefString::from(error_message));
+        query
+            .frame
+            .send_process_message(CefProcessId::PID_RENDERER, message);
+        Ok(())
+    }
+}
@@ -0,0 +1,267 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use super::MessageRouterConfig;
+use crate::{
+    prelude::*, register_js_callback, CefFrame, CefProcessId, CefProcessMessage, CefV8Context,
+    CefV8PropertyAttribute, JsCallbackId, V8Handler, V8Value,
+};
+
+#[derive(Clone)]
+struct PendingRequest {
+    context: CefV8Context,
+    frame: CefFrame,
+    persistent: bool,
+    on_success: Option<JsCallbackId>,
+    on_failure: Option<JsCallbackId>,
+}
+
+impl PendingRequest {
+    /// Drop the JS callbacks of a completed request.
+    fn remove_callbacks(&self) {
+        for id in [self.on_success, self.on_failure].into_iter().flatten() {
+            _ = id.remove();
+        }
+    }
+}
+
+static NEXT_REQUEST_ID: AtomicI32 = AtomicI32::new(1);
+
+/// Renderer process half of the message router, see the [module docs](crate::MessageRouterConfig).
+///
+/// Clones share their pending requests. All methods must be called on the renderer thread.
+#[derive(Clone)]
+pub struct MessageRouterRendererSide {
+    config: Arc<MessageRouterConfig>,
+    pending: Arc<Mutex<BTreeMap<i32, PendingRequest>>>,
+}
+
+impl MessageRouterRendererSide {
+    pub fn new(config: MessageRouterConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            pending: Arc::default(),
+        }
+    }
+
+    /// Call from [CefRenderProcessHandler::on_context_created](crate::CefRenderProcessHandler::on_context_created)
+    /// to install the JS functions on the global object of `context`.
+    pub fn on_context_created(&self, context: &CefV8Context) -> Result<()> {
+        let _scope = context.scope()?;
+        let global = context.get_global().ok_or(Error::NullPtr)?;
+
+        let router = self.clone();
+        let query =
+            V8Handler::new(move |_, _, _, args| router.query(args).map_err(anyhow::Error::msg));
+        global.set_value_bykey(
+            &self.config.js_query_function,
+            V8Value::function(&self.config.js_query_function, query),
+            CefV8PropertyAttribute::V8_PROPERTY_ATTRIBUTE_READONLY,
+        );
+
+        let router = self.clone();
+        let cancel =
+            V8Handler::new(move |_, _, _, args| router.cancel(args).map_err(anyhow::Error::msg));
+        global.set_value_bykey(
+            &self.config.js_cancel_function,
+            V8Value::function(&self.config.js_cancel_function, cancel),
+            CefV8PropertyAttribute::V8_PROPERTY_ATTRIBUTE_READONLY,
+        );
+        Ok(())
+    }
+
+    /// Call from [CefRenderProcessHandler::on_context_released](crate::CefRenderProcessHandler::on_context_released)
+    /// to cancel the pending requests of `context`.
+    pub fn on_context_released(&self, context: &CefV8Context) {
+        let released: Vec<(i32, PendingRequest)> = {
+            let mut pending = self.pending.lock().unwrap();
+            let ids: Vec<i32> = pending
+                .iter()
+                .filter(|(_, request)| request.context.is_same(context.clone()).unwrap_or_default())
+                .map(|(id, _)| *id)
+                .collect();
+            ids.into_iter()
+                .filter_map(|id| pending.remove(&id).map(|request| (id, request)))
+                .collect()
+        };
+        for (request_id, request) in released {
+            self.send_cancel(request_id, &request);
+        }
+    }
+
+    /// Call from [CefRenderProcessHandler::on_process_message_received](crate::CefRenderProcessHandler::on_process_message_received).
+    ///
+    /// Returns false for messages not sent by a [MessageRouterBrowserSide](crate::MessageRouterBrowserSide).
+    pub fn on_process_message_received(&self, message: &CefProcessMessage) -> bool {
+        let name = message.get_name().unwrap_or_default().to_string();
+        if name != self.config.query_message_name() {
+            return false;
+        }
+        let Some(arguments) = message.get_argument_list() else {
+            return false;
+        };
+        let request_id = arguments.get_int(0).unwrap_or_default();
+        let success = arguments.get_bool(1).unwrap_or_default();
+        let request = {
+            let mut pending = self.pending.lock().unwrap();
+            match pending.get(&request_id) {
+                Some(request) if success && request.persistent => Some(request.clone()),
+                Some(_) => pending.remove(&request_id),
+                None => None,
+            }
+        };
+        // The request was cancelled in the meantime.
+        let Some(request) = request else {
+            return true;
+        };
+        if success {
+            let response = arguments.get_string(2).unwrap_or_default().to_string();
+            if let Some(id) = request.on_success {
+                invoke(id, || vec![V8Value::string(&response)]);
+            }
+        } else {
+            let error_code = arguments.get_int(2).unwrap_or_default();
+            let error_message = arguments.get_string(3).unwrap_or_default().to_string();
+            if let Some(id) = request.on_failure {
+                invoke(id, || {
+                    vec![V8Value::int(error_code), V8Value::string(&error_message)]
+                });
+            }
+        }
+        if !success || !request.persistent {
+            request.remove_callbacks();
+        }
+        true
+    }
+
+    /// `cefQuery({request, persistent, onSuccess, onFailure})`, returning the request id.
+    fn query(&self, args: &[V8Value]) -> std::result::Result<V8Value, String> {
+        let name = &self.config.js_query_function;
+        let [options] = args else {
+            return Err(format!("{name}: expected 1 argument, got {}", args.len()));
+        };
+        if !options.is_object().unwrap_or_default() {
+            return Err(format!("{name}: expected an object"));
+        }
+        let field = |key: &str| {
+            options
+                .get_value_bykey(key)
+                .filter(|value| !value.is_undefined().unwrap_or(true))
+        };
+
+        let request = field("request")
+            .filter(|value| value.is_string().unwrap_or_default())
+            .and_then(|value| value.get_string_value())
+            .ok_or_else(|| format!("{name}: `request` must be a string"))?;
+        let persistent = match field("persistent") {
+            None => false,
+            Some(value) if value.is_bool().unwrap_or_default() => {
+                value.get_bool_value().unwrap_or_default()
+            }
+            Some(_) => return Err(format!("{name}: `persistent` must be a boolean")),
+        };
+        let function = |key: &str| match field(key) {
+            None => Ok(None),
+            Some(value) if value.is_function().unwrap_or_default() => Ok(Some(value)),
+            Some(_) => Err(format!("{name}: `{key}` must be a function")),
+        };
+        let on_success = function("onSuccess")?;
+        let on_failure = function("onFailure")?;
+
+        let context = CefV8Context::get_current();
+        let frame = context
+            .get_frame()
+            .ok_or_else(|| format!("{name}: no frame"))?;
+        let request_id = NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed);
+        let message = query_message(&self.config, request_id, &request.to_string(), persistent)
+            .map_err(|e| format!("{name}: {e}"))?;
+        let register = |function: Option<V8Value>| {
+            function
+                .map(register_js_callback)
+                .transpose()
+                .map_err(|e| format!("{name}: {e}"))
+        };
+        let request = PendingRequest {
+            context,
+            frame: frame.clone(),
+            persistent,
+            on_success: register(on_success)?,
+            on_failure: register(on_failure)?,
+        };
+        self.pending.lock().unwrap().insert(request_id, request);
+        frame.send_process_message(CefProcessId::PID_BROWSER, message);
+        Ok(V8Value::int(request_id))
+    }
+
+    /// `cefQueryCancel(id)`, returning whether a pending request of the current context was
+    /// cancelled.
+    fn cancel(&self, args: &[V8Value]) -> std::result::Result<V8Value, String> {
+        let name = &self.config.js_cancel_function;
+        let [id] = args else {
+            return Err(format!("{name}: expected 1 argument, got {}", args.len()));
+        };
+        let request_id = id
+            .is_int()
+            .unwrap_or_default()
+            .then(|| id.get_int_value())
+            .flatten()
+            .ok_or_else(|| format!("{name}: expected a request id"))?;
+        let context = CefV8Context::get_current();
+        let request = {
+            let mut pending = self.pending.lock().unwrap();
+            let current = pending.get(&request_id).is_some_and(|request| {
+                request.context.is_same(context.clone()).unwrap_or_default()
+            });
+            current.then(|| pending.remove(&request_id)).flatten()
+        };
+        if let Some(request) = &request {
+            self.send_cancel(request_id, request);
+        }
+        Ok(V8Value::bool(request.is_some()))
+    }
+
+    /// Tell the browser process `request` was cancelled, and drop its callbacks.
+    fn send_cancel(&self, request_id: i32, request: &PendingRequest) {
+        request.remove_callbacks();
+        let Ok(message) = CefProcessMessage::create(&self.config.cancel_message_name()) else {
+            return;
+        };
+        if let Some(arguments) = message.get_argument_list() {
+            arguments.set_int(0, request_id);
+            request
+                .frame
+                .send_process_message(CefProcessId::PID_BROWSER, message);
+        }
+    }
+}
+
+/// Encode a new request.
+fn query_message(
+    config: &MessageRouterConfig,
+    request_id: i32,
+    request: &str,
+    persistent: bool,
+) -> Result<CefProcessMessage> {
+    let message = CefProcessMessage::create(&config.query_message_name())?;
+    let arguments = message.get_argument_list().ok_or(Error::NullPtr)?;
+    arguments.set_int(0, request_id);
+    arguments.set_string(1, CefString::from(request));
+    arguments.set_bool(2, persistent);
+    Ok(message)
+}
+
+/// Call a JS callback right away, with its context entered.
+fn invoke(id: JsCallbackId, args: impl FnOnce() -> Vec<V8Value>) {
+    let Some(callback) = id.get() else {
+        return;
+    };
+    let Ok(_scope) = callback.context.scope() else {
+        return;
+    };
+    callback.function.execute_function(None, args());
+}
@@ -2,7 +2,7 @@ use cef_sys::{
     _cef_string_multimap_t, cef_string_multimap_alloc, cef_string_multimap_append,
     cef_string_multimap_clear, cef_string_multimap_enumerate, cef_string_multimap_find_count,
     cef_string_multimap_free, cef_string_multimap_key, cef_string_multimap_size,
-    cef_string_multimap_value,
+    cef_string_multimap_value, cef_string_utf16_clear,
 };
 use std::ptr::NonNull;
 
@@ -82,45 +82,51 @@ impl CefStringMultiMap {
     /// See [cef_string_multimap_key]
     pub fn key(&self, index: usize) -> Option<CefString> {
         let Some(map) = self.ptr else { return None };
-        let key = std::ptr::null_mut();
         unsafe {
-            if cef_string_multimap_key(map.as_ptr(), index, key) == 1 {
-                CefString::from_raw(key)
+            let mut key = Default::default();
+            let result = if cef_string_multimap_key(map.as_ptr(), index, &mut key) == 1 {
+                CefString::from_raw(&key)
             } else {
                 None
-            }
+            };
+            cef_string_utf16_clear(&mut key);
+            result
         }
     }
 
     /// See [cef_string_multimap_value]
     pub fn value(&self, index: usize) -> Option<CefString> {
         let Some(map) = self.ptr else { return None };
-        let value = std::ptr::null_mut();
         unsafe {
-            if cef_string_multimap_value(map.as_ptr(), index, value) == 1 {
-                CefString::from_raw(value)
+            let mut value = Default::default();
+            let result = if cef_string_multimap_value(map.as_ptr(), index, &mut value) == 1 {
+                CefString::from_raw(&value)
             } else {
                 None
-            }
+            };
+            cef_string_utf16_clear(&mut value);
+            result
         }
     }
 
     /// See [cef_string_multimap_enumerate]
     pub fn enumerate(&self, key: &str, index: usize) -> Option<CefString> {
         let Some(map) = self.ptr else { return None };
-        let value = std::ptr::null_mut();
         unsafe {
-            if cef_string_multimap_enumerate(
+            let mut value = Default::default();
+            let result = if cef_string_multimap_enumerate(
                 map.as_ptr(),
                 &CefString::from(key).as_raw(),
                 index,
-                value,
+                &mut value,
             ) == 1
             {
-                CefString::from_raw(value)
+                CefString::from_raw(&value)
             } else {
                 None
-            }
+            };
+            cef_string_utf16_clear(&mut value);
+            result
         }
     }
 }
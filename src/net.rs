@@ -1,7 +1,11 @@
 mod cookie;
 mod post_data;
 mod request;
+mod response;
+mod url_request;
 
 pub use cookie::*;
 pub use post_data::*;
 pub use request::*;
+pub use response::*;
+pub use url_request::*;
@@ -80,9 +80,9 @@ impl CefRequest {
         /// See [cef_request_t::get_header_map]
         fn get_header_map(&self) -> crate::multimap::CefStringMultiMap {
             get_header_map.map(|f| unsafe {
-                let map = std::ptr::null_mut();
-                f(self.get_this(), map);
-                crate::multimap::CefStringMultiMap::from_raw(map)
+                let map = crate::multimap::CefStringMultiMap::new();
+                f(self.get_this(), map.as_raw());
+                map
             })
         }
 
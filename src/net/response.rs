@@ -0,0 +1,112 @@
+use crate::{multimap::CefStringMultiMap, prelude::*, CefErrorCode};
+
+/// See [cef_response_t] for more docs.
+#[derive(Debug, Clone)]
+#[wrapper]
+pub struct CefResponse(cef_response_t);
+
+impl CefResponse {
+    /// See [cef_response_create]
+    pub fn create() -> Result<CefResponse> {
+        let ptr = unsafe { cef_response_create() };
+        if ptr.is_null() {
+            Err(crate::error::Error::NullPtr)
+        } else {
+            Ok(CefResponse::from(ptr))
+        }
+    }
+}
+
+impl CefResponse {
+    wrapper_methods! {
+        /// See [cef_response_t::is_read_only]
+        fn is_read_only(&self) -> bool;
+
+        /// See [cef_response_t::get_error]
+        fn get_error(&self) -> CefErrorCode;
+
+        /// See [cef_response_t::set_error]
+        fn set_error(&self, error: CefErrorCode);
+
+        /// See [cef_response_t::get_status]
+        fn get_status(&self) -> i32;
+
+        /// See [cef_response_t::set_status]
+        fn set_status(&self, status: i32);
+
+        /// See [cef_response_t::get_status_text]
+        fn get_status_text(&self) -> CefString {
+            get_status_text.and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_response_t::set_status_text]
+        fn set_status_text(&self, status_text: &str) {
+            set_status_text
+                .map(|f| unsafe { f(self.get_this(), &CefString::from(status_text).as_raw()) })
+        }
+
+        /// See [cef_response_t::get_mime_type]
+        fn get_mime_type(&self) -> CefString {
+            get_mime_type.and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_response_t::set_mime_type]
+        fn set_mime_type(&self, mime_type: &str) {
+            set_mime_type
+                .map(|f| unsafe { f(self.get_this(), &CefString::from(mime_type).as_raw()) })
+        }
+
+        /// See [cef_response_t::get_charset]
+        fn get_charset(&self) -> CefString {
+            get_charset.and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_response_t::set_charset]
+        fn set_charset(&self, charset: &str) {
+            set_charset.map(|f| unsafe { f(self.get_this(), &CefString::from(charset).as_raw()) })
+        }
+
+        /// See [cef_response_t::get_header_by_name]
+        fn get_header_by_name(&self, name: &str) -> CefString {
+            get_header_by_name.and_then(|f| unsafe {
+                CefString::from_userfree_cef(f(self.get_this(), &CefString::from(name).as_raw()))
+            })
+        }
+
+        /// See [cef_response_t::set_header_by_name]
+        fn set_header_by_name(&self, name: &str, value: &str, overwrite: bool) {
+            set_header_by_name.map(|f| unsafe {
+                f(
+                    self.get_this(),
+                    &CefString::from(name).as_raw(),
+                    &CefString::from(value).as_raw(),
+                    overwrite as _,
+                )
+            })
+        }
+
+        /// See [cef_response_t::get_header_map]
+        fn get_header_map(&self) -> CefStringMultiMap {
+            get_header_map.map(|f| unsafe {
+                let map = CefStringMultiMap::new();
+                f(self.get_this(), map.as_raw());
+                map
+            })
+        }
+
+        /// See [cef_response_t::set_header_map]
+        fn set_header_map(&self, header_map: CefStringMultiMap) {
+            set_header_map.map(|f| unsafe { f(self.get_this(), header_map.as_raw()) })
+        }
+
+        /// See [cef_response_t::get_url]
+        fn get_url(&self) -> CefString {
+            get_url.and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_response_t::set_url]
+        fn set_url(&self, url: &str) {
+            set_url.map(|f| unsafe { f(self.get_this(), &CefString::from(url).as_raw()) })
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll},
+};
+
+use crate::{oneshot, prelude::*, CefErrorCode};
+
+use super::{CefRequest, CefRequestContext, CefResponse};
+
+/// See [cef_urlrequest_status_t]
+pub type CefUrlRequestStatus = cef_urlrequest_status_t;
+
+/// See [cef_urlrequest_t] for more docs.
+#[derive(Debug, Clone)]
+#[wrapper]
+pub struct CefUrlRequest(cef_urlrequest_t);
+
+impl CefUrlRequest {
+    /// See [cef_urlrequest_create]
+    ///
+    /// Creates a request that isn't associated with any browser or frame. Use
+    /// [CefFrame::create_urlrequest](crate::CefFrame::create_urlrequest) for a frame-bound
+    /// request. Pass `None` as `request_context` to use the global request context.
+    pub fn create(
+        request: CefRequest,
+        client: impl CefUrlRequestClient,
+        request_context: Option<CefRequestContext>,
+    ) -> Result<Self> {
+        let ptr = unsafe {
+            cef_urlrequest_create(
+                request.into_raw(),
+                client.into_raw(),
+                request_context
+                    .map(|c| c.into_raw())
+                    .unwrap_or(std::ptr::null_mut()),
+            )
+        };
+        if ptr.is_null() {
+            Err(Error::NullPtr)
+        } else {
+            Ok(Self::from(ptr))
+        }
+    }
+
+    wrapper_methods! {
+        /// See [cef_urlrequest_t::get_request]
+        fn get_request(&self) -> CefRequest {
+            get_request.and_then(|f| unsafe {
+                let request = f(self.get_this());
+                if request.is_null() {
+                    None
+                } else {
+                    Some(CefRequest::from(request))
+                }
+            })
+        }
+
+        /// See [cef_urlrequest_t::get_request_status]
+        fn get_request_status(&self) -> CefUrlRequestStatus;
+
+        /// See [cef_urlrequest_t::get_request_error]
+        fn get_request_error(&self) -> CefErrorCode;
+
+        /// See [cef_urlrequest_t::get_response]
+        fn get_response(&self) -> CefResponse {
+            get_response.and_then(|f| unsafe {
+                let response = f(self.get_this());
+                if response.is_null() {
+                    None
+                } else {
+                    Some(CefResponse::from(response))
+                }
+            })
+        }
+
+        /// See [cef_urlrequest_t::response_was_cached]
+        fn response_was_cached(&self) -> bool;
+
+        /// See [cef_urlrequest_t::cancel]
+        fn cancel(&self);
+    }
+}
+
+/// See [cef_auth_callback_t] for more docs.
+#[derive(Debug, Clone)]
+#[wrapper]
+pub struct CefAuthCallback(cef_auth_callback_t);
+
+impl CefAuthCallback {
+    wrapper_methods! {
+        /// See [cef_auth_callback_t::cont]
+        fn cont(&self, username: &str, password: &str) {
+            cont.map(|f| unsafe {
+                f(
+                    self.get_this(),
+                    &CefString::from(username).as_raw(),
+                    &CefString::from(password).as_raw(),
+                )
+            })
+        }
+
+        /// See [cef_auth_callback_t::cancel]
+        fn cancel(&self);
+    }
+}
+
+/// See [cef_urlrequest_client_t] for more docs.
+#[allow(unused_variables)]
+pub trait CefUrlRequestClient: Sized {
+    /// See [cef_urlrequest_client_t::on_request_complete]
+    fn on_request_complete(&self, request: CefUrlRequest);
+
+    /// See [cef_urlrequest_client_t::on_upload_progress]
+    fn on_upload_progress(&self, request: CefUrlRequest, current: i64, total: i64) {}
+
+    /// See [cef_urlrequest_client_t::on_download_progress]
+    fn on_download_progress(&self, request: CefUrlRequest, current: i64, total: i64) {}
+
+    /// See [cef_urlrequest_client_t::on_download_data]
+    fn on_download_data(&self, request: CefUrlRequest, data: &[u8]) {}
+
+    /// See [cef_urlrequest_client_t::get_auth_credentials]
+    fn get_auth_credentials(
+        &self,
+        is_proxy: bool,
+        host: CefString,
+        port: i32,
+        realm: Option<CefString>,
+        scheme: Option<CefString>,
+        callback: CefAuthCallback,
+    ) -> bool {
+        false
+    }
+
+    #[doc(hidden)]
+    fn into_raw(self) -> *mut cef_urlrequest_client_t {
+        unsafe extern "C" fn on_request_complete<I: CefUrlRequestClient>(
+            self_: *mut _cef_urlrequest_client_t,
+            request: *mut _cef_urlrequest_t,
+        ) {
+            let client: &crate::rc::RcImpl<_, I> = crate::rc::RcImpl::get(self_);
+            client
+                .interface
+                .on_request_complete(CefUrlRequest::from(request));
+        }
+
+        unsafe extern "C" fn on_upload_progress<I: CefUrlRequestClient>(
+            self_: *mut _cef_urlrequest_client_t,
+            request: *mut _cef_urlrequest_t,
+            current: i64,
+            total: i64,
+        ) {
+            let client: &crate::rc::RcImpl<_, I> = crate::rc::RcImpl::get(self_);
+            client
+                .interface
+                .on_upload_progress(CefUrlRequest::from(request), current, total);
+        }
+
+        unsafe extern "C" fn on_download_progress<I: CefUrlRequestClient>(
+            self_: *mut _cef_urlrequest_client_t,
+            request: *mut _cef_urlrequest_t,
+            current: i64,
+            total: i64,
+        ) {
+            let client: &crate::rc::RcImpl<_, I> = crate::rc::RcImpl::get(self_);
+            client
+                .interface
+                .on_download_progress(CefUrlRequest::from(request), current, total);
+        }
+
+        unsafe extern "C" fn on_download_data<I: CefUrlRequestClient>(
+            self_: *mut _cef_urlrequest_client_t,
+            request: *mut _cef_urlrequest_t,
+            data: *const ::std::os::raw::c_void,
+            data_length: usize,
+        ) {
+            let client: &crate::rc::RcImpl<_, I> = crate::rc::RcImpl::get(self_);
+            let data = if data.is_null() {
+                &[]
+            } else {
+                std::slice::from_raw_parts(data.cast(), data_length)
+            };
+            client
+                .interface
+                .on_download_data(CefUrlRequest::from(request), data);
+        }
+
+        unsafe extern "C" fn get_auth_credentials<I: CefUrlRequestClient>(
+            self_: *mut _cef_urlrequest_client_t,
+            is_proxy: ::std::os::raw::c_int,
+            host: *const cef_string_t,
+            port: ::std::os::raw::c_int,
+            realm: *const cef_string_t,
+            scheme: *const cef_string_t,
+            callback: *mut _cef_auth_callback_t,
+        ) -> ::std::os::raw::c_int {
+            let client: &crate::rc::RcImpl<_, I> = crate::rc::RcImpl::get(self_);
+            client.interface.get_auth_credentials(
+                is_proxy == 1,
+                CefString::from_raw(host).unwrap_or_default(),
+                port,
+                CefString::from_raw(realm),
+                CefString::from_raw(scheme),
+                CefAuthCallback::from(callback),
+            ) as _
+        }
+
+        let mut object: cef_urlrequest_client_t = unsafe { std::mem::zeroed() };
+        object.on_request_complete = Some(on_request_complete::<Self>);
+        object.on_upload_progress = Some(on_upload_progress::<Self>);
+        object.on_download_progress = Some(on_download_progress::<Self>);
+        object.on_download_data = Some(on_download_data::<Self>);
+        object.get_auth_credentials = Some(get_auth_credentials::<Self>);
+        crate::rc::RcImpl::new(object, self).cast()
+    }
+}
+
+/// Send `request` with the global request context and collect the response body.
+///
+/// Must be called from the browser process. See [CefUrlRequest::create].
+pub fn fetch(request: CefRequest) -> FetchResponse {
+    let (sender, response) = oneshot::channel();
+    let client = FetchClient {
+        sender,
+        body: Mutex::new(Vec::new()),
+    };
+    match CefUrlRequest::create(request, client, None) {
+        Ok(request) => FetchResponse {
+            request: Some(request),
+            response,
+        },
+        Err(e) => FetchResponse {
+            request: None,
+            response: oneshot::Receiver::ready(Err(e)),
+        },
+    }
+}
+
+struct FetchClient {
+    sender: oneshot::Sender<Result<(CefResponse, Vec<u8>)>>,
+    body: Mutex<Vec<u8>>,
+}
+
+impl CefUrlRequestClient for FetchClient {
+    fn on_request_complete(&self, request: CefUrlRequest) {
+        let body = std::mem::take(&mut *self.body.lock().unwrap());
+        let status = request
+            .get_request_status()
+            .unwrap_or(CefUrlRequestStatus::UR_UNKNOWN);
+        let result = match (status, request.get_response()) {
+            (CefUrlRequestStatus::UR_SUCCESS, Some(response)) => Ok((response, body)),
+            (status, _) => Err(Error::UrlRequest {
+                status,
+                error: request
+                    .get_request_error()
+                    .unwrap_or(CefErrorCode::ERR_NONE),
+            }),
+        };
+        self.sender.send(result);
+    }
+
+    fn on_download_data(&self, _request: CefUrlRequest, data: &[u8]) {
+        self.body.lock().unwrap().extend_from_slice(data);
+    }
+}
+
+/// The future returned by [fetch].
+///
+/// Dropping it before completion cancels the request.
+pub struct FetchResponse {
+    request: Option<CefUrlRequest>,
+    response: oneshot::Receiver<Result<(CefResponse, Vec<u8>)>>,
+}
+
+impl Future for FetchResponse {
+    type Output = Result<(CefResponse, Vec<u8>)>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.response).poll(cx)
+    }
+}
+
+impl Drop for FetchResponse {
+    fn drop(&mut self) {
+        if self.response.is_sent() {
+            return;
+        }
+        if let Some(request) = self.request.take() {
+            request.cancel();
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Channel sending a single value to a future
+//!
+//! Backs the futures completed by CEF callbacks, e.g. [DevToolsResponse](crate::DevToolsResponse)
+//! or [StringFuture](crate::StringFuture).
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+struct Slot<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+    sent: bool,
+}
+
+/// Create a [Receiver] resolving with the value passed to its [Sender].
+pub(crate) fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let slot = Arc::new(Mutex::new(Slot {
+        value: None,
+        waker: None,
+        sent: false,
+    }));
+    (Sender(slot.clone()), Receiver(slot))
+}
+
+/// Sending half of a [channel].
+pub(crate) struct Sender<T>(Arc<Mutex<Slot<T>>>);
+
+impl<T> Sender<T> {
+    /// Complete the [Receiver] with `value`, unless a value was already sent.
+    pub(crate) fn send(&self, value: T) {
+        let mut slot = self.0.lock().unwrap();
+        if slot.sent {
+            return;
+        }
+        slot.sent = true;
+        slot.value = Some(value);
+        if let Some(waker) = slot.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Receiving half of a [channel], a future resolving with the sent value.
+pub(crate) struct Receiver<T>(Arc<Mutex<Slot<T>>>);
+
+impl<T> Receiver<T> {
+    /// A receiver resolving with `value` right away.
+    pub(crate) fn ready(value: T) -> Self {
+        let (sender, receiver) = channel();
+        sender.send(value);
+        receiver
+    }
+
+    /// `true` once a value was sent, even if it was already received.
+    pub(crate) fn is_sent(&self) -> bool {
+        self.0.lock().unwrap().sent
+    }
+}
+
+impl<T> Future for Receiver<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.0.lock().unwrap();
+        match slot.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -1,5 +1,20 @@
+use std::os::raw::c_void;
+
 use crate::prelude::*;
 
+#[cfg(feature = "serde")]
+mod command;
+#[cfg(feature = "serde")]
+mod ipc;
+#[cfg(feature = "serde")]
+mod rpc;
+#[cfg(feature = "serde")]
+pub use command::*;
+#[cfg(feature = "serde")]
+pub use ipc::*;
+#[cfg(feature = "serde")]
+pub use rpc::*;
+
 /// See [cef_process_message_t] for more docs.
 #[derive(Debug, Clone)]
 #[wrapper]
@@ -62,8 +77,130 @@ impl CefProcessMessage {
                 }
             })
         }
+
+        /// See [cef_process_message_t::get_shared_memory_region]
+        ///
+        /// Only messages built with a [CefSharedProcessMessageBuilder] have a shared memory region,
+        /// their argument list is empty.
+        fn get_shared_memory_region(&self) -> CefSharedMemoryRegion {
+            if !self.is_valid().unwrap_or_default() {
+                return None;
+            }
+            get_shared_memory_region.and_then(|f| unsafe {
+                let ptr = f(self.get_this());
+                if ptr.is_null() {
+                    None
+                } else {
+                    Some(CefSharedMemoryRegion::from(ptr))
+                }
+            })
+        }
+    );
+}
+
+/// See [cef_shared_memory_region_t] for more docs.
+#[derive(Debug, Clone)]
+#[wrapper]
+pub struct CefSharedMemoryRegion(cef_shared_memory_region_t);
+
+impl CefSharedMemoryRegion {
+    /// View of the whole region, empty for invalid instances.
+    pub fn as_bytes(&self) -> &[u8] {
+        if !self.is_valid().unwrap_or_default() {
+            return &[];
+        }
+        match (self.memory(), self.size()) {
+            (Some(ptr), Some(len)) if !ptr.is_null() => unsafe {
+                std::slice::from_raw_parts(ptr.cast(), len)
+            },
+            _ => &[],
+        }
+    }
+
+    wrapper_methods!(
+        /// See [cef_shared_memory_region_t::is_valid]
+        fn is_valid(&self) -> bool;
+
+        /// See [cef_shared_memory_region_t::size]
+        fn size(&self) -> usize;
+
+        /// See [cef_shared_memory_region_t::memory]
+        fn memory(&self) -> *mut c_void;
+    );
+}
+
+/// See [cef_shared_process_message_builder_t] for more docs.
+///
+/// Builds a process message carrying `byte_size` bytes of shared memory, which avoids copying
+/// large payloads through the argument list. The receiver reads them back with
+/// [CefProcessMessage::get_shared_memory_region].
+///
+/// ```no_run
+/// use cef::{CefFrame, CefProcessId, CefSharedProcessMessageBuilder};
+///
+/// fn send_frame(frame: &CefFrame, pixels: &[u8]) -> cef::Result<()> {
+///     let mut builder = CefSharedProcessMessageBuilder::create("app.pixels", pixels.len())?;
+///     builder.as_bytes_mut().copy_from_slice(pixels);
+///     frame.send_process_message(CefProcessId::PID_RENDERER, builder.build()?);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+#[wrapper]
+pub struct CefSharedProcessMessageBuilder(cef_shared_process_message_builder_t);
+
+impl CefSharedProcessMessageBuilder {
+    /// See [cef_shared_process_message_builder_create]
+    pub fn create(name: &str, byte_size: usize) -> Result<CefSharedProcessMessageBuilder> {
+        let name = CefString::from(name);
+        unsafe {
+            let builder = cef_shared_process_message_builder_create(&name.as_raw(), byte_size);
+            if builder.is_null() {
+                return Err(Error::NullPtr);
+            }
+            Ok(CefSharedProcessMessageBuilder::from(builder))
+        }
+    }
+
+    /// Writable view of the whole region, empty for invalid instances.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        if !self.is_valid().unwrap_or_default() {
+            return &mut [];
+        }
+        match (self.memory(), self.size()) {
+            (Some(ptr), Some(len)) if !ptr.is_null() => unsafe {
+                std::slice::from_raw_parts_mut(ptr.cast(), len)
+            },
+            _ => &mut [],
+        }
+    }
+
+    /// See [cef_shared_process_message_builder_t::build]
+    pub fn build(self) -> Result<CefProcessMessage> {
+        if !self.is_valid().unwrap_or_default() {
+            return Err(Error::NullPtr);
+        }
+        let Some(inner) = &self.0 else {
+            return Err(Error::NullPtr);
+        };
+        let build = inner.build.ok_or(Error::NullPtr)?;
+        unsafe {
+            let message = build(self.get_this());
+            if message.is_null() {
+                return Err(Error::NullPtr);
+            }
+            Ok(CefProcessMessage::from(message))
+        }
+    }
+
+    wrapper_methods!(
+        /// See [cef_shared_process_message_builder_t::is_valid]
+        fn is_valid(&self) -> bool;
+
+        /// See [cef_shared_process_message_builder_t::size]
+        fn size(&self) -> usize;
+
+        /// See [cef_shared_process_message_builder_t::memory]
+        fn memory(&self) -> *mut c_void;
     );
-    // TODO
-    // See [cef_process_message_t::get_shared_memory_region]
-    //fn get_shared_memory_region(&self) -> cef_sys::cef_shared_memory_region_t;
 }
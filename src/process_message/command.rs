@@ -0,0 +1,419 @@
+//! Rust commands invoked from JS
+//!
+//! [`#[command]`](crate::command) turns a free function of the browser process into a
+//! [Command], registered in [Commands] and served by an [IpcRouter] through
+//! [IpcRouter::with_commands]. In the renderer process, [register_invoke] installs
+//! `cef.invoke(command, args)`, which returns a promise settled with the result of the command:
+//!
+//! ```js
+//! const greeting = await cef.invoke("greet", { name: "world" });
+//! ```
+//!
+//! The properties of `args` are deserialized into the arguments of the same name, and the return
+//! value is serialized back. An `Err` return, a malformed argument, an unknown command or a
+//! command the origin of the calling frame isn't allowed to invoke rejects the promise.
+//!
+//! Every command lists the origins allowed to invoke it, e.g. `https://app.example`, with `*`
+//! allowing any. Commands without origins can't be invoked until [Commands::allow] adds some.
+//! The origin of a call is the one of the document calling `cef.invoke`, which must still be
+//! loaded in its frame when the browser process receives the call. URLs without a host, such
+//! as `about:blank` and `data:` URLs, have the `null` origin.
+//!
+//! Commands run on the UI thread of the browser process, async ones being polled there by a
+//! minimal executor without any I/O reactor or timer. They must not block, and futures relying
+//! on a runtime, such as tokio's I/O, must run on that runtime with the command awaiting their
+//! result.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use cef::{command, register_invoke, CefV8Context, Commands, IpcRouter};
+//!
+//! #[command(origins = ["https://app.example"])]
+//! fn greet(name: String) -> String {
+//!     format!("Hello, {name}!")
+//! }
+//!
+//! #[command(name = "readFile", origins = ["https://app.example"])]
+//! async fn read_file(path: String) -> Result<String, std::io::Error> {
+//!     spawn_blocking(move || std::fs::read_to_string(path)).await
+//! }
+//!
+//! // Runs `f` on a thread pool, e.g. the one of the async runtime of the app, and resolves with
+//! // its result.
+//! # async fn spawn_blocking<T>(f: impl FnOnce() -> T) -> T { f() }
+//!
+//! // Browser process, with `dispatch` called from `CefClient::on_process_message_received`
+//! fn router() -> IpcRouter {
+//!     let commands = Commands::new()
+//!         .command(greet::command())
+//!         .command(read_file::command())
+//!         .allow("greet", ["http://localhost:8080"]);
+//!     IpcRouter::new().with_commands(commands)
+//! }
+//!
+//! // Renderer process, from `CefRenderProcessHandler::on_context_created`. Responses are routed
+//! // by an `IpcRouter` dispatching from `CefRenderProcessHandler::on_process_message_received`.
+//! fn context_created(context: CefV8Context) -> cef::Result<()> {
+//!     register_invoke(&context, Duration::from_secs(30))
+//! }
+//! ```
+
+use std::{collections::HashMap, future::Future, pin::Pin, time::Duration};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{IpcMessage, IpcRequest, IpcRouter, RpcResponder};
+use crate::{
+    prelude::*,
+    task::{post_task, spawn_local},
+    v8::{in_context, object_at_path},
+    CefFrame, CefProcessId, CefThreadId, CefV8Context, CefV8PropertyAttribute, IntoV8Value,
+    PromiseHandle, TsParam, V8Handler, V8Value,
+};
+
+/// Outcome of a [Command], the value or the error message.
+pub type CommandResult = std::result::Result<serde_json::Value, String>;
+
+/// Future running a [Command].
+pub type CommandFuture = Pin<Box<dyn Future<Output = CommandResult> + Send>>;
+
+/// The `args` object of an invoke call, see [`#[command]`](crate::command).
+#[derive(Debug, Clone)]
+pub struct CommandArgs(serde_json::Value);
+
+/// A Rust function invoked from JS, generated by [`#[command]`](crate::command).
+#[derive(Debug, Clone, Copy)]
+pub struct Command {
+    /// Name of the command in JS.
+    pub name: &'static str,
+    /// Origins allowed to invoke the command, `*` allowing any.
+    pub origins: &'static [&'static str],
+    /// Deserialize the arguments and start the Rust function.
+    pub call: fn(CommandArgs) -> CommandFuture,
+    /// The properties of `args`, for [TypeScriptDeclarations](crate::TypeScriptDeclarations).
+    pub params: &'static [TsParam],
+    /// TypeScript type of the result.
+    pub returns: &'static str,
+}
+
+/// Deserialize argument `name` of a [Command], a missing argument being `null`.
+#[doc(hidden)]
+pub fn command_argument<T: DeserializeOwned>(
+    command: &str,
+    args: &CommandArgs,
+    name: &str,
+) -> std::result::Result<T, String> {
+    let value = args.0.get(name).cloned().unwrap_or_default();
+    serde_json::from_value(value).map_err(|e| format!("{command}: argument `{name}`: {e}"))
+}
+
+/// Serialize the return value of a [Command].
+#[doc(hidden)]
+pub fn command_output<T: Serialize>(output: T) -> CommandResult {
+    serde_json::to_value(output).map_err(|e| e.to_string())
+}
+
+/// Serialize the `Ok` value of a [Command], or return the `Err` message.
+#[doc(hidden)]
+pub fn command_result<T: Serialize, E: std::fmt::Display>(
+    output: std::result::Result<T, E>,
+) -> CommandResult {
+    output.map_err(|e| e.to_string()).and_then(command_output)
+}
+
+/// The request sent by `cef.invoke`.
+#[derive(Serialize, Deserialize)]
+struct Invoke {
+    command: String,
+    args: serde_json::Value,
+    /// Origin of the document calling `cef.invoke`.
+    origin: String,
+}
+
+impl IpcMessage for Invoke {
+    const NAME: &'static str = "cef.invoke";
+}
+
+impl IpcRequest for Invoke {
+    type Response = serde_json::Value;
+}
+
+/// The [Command]s served by an [IpcRouter], with the origins allowed to invoke each of them.
+#[derive(Debug, Clone, Default)]
+pub struct Commands {
+    commands: HashMap<&'static str, (Command, Vec<String>)>,
+}
+
+impl Commands {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `command`, allowed for its own origins, replacing the previous command with the
+    /// same name.
+    pub fn command(mut self, command: Command) -> Self {
+        let origins = command.origins.iter().map(|origin| origin.to_string());
+        self.commands
+            .insert(command.name, (command, origins.collect()));
+        self
+    }
+
+    /// Also allow `origins` to invoke the registered command `name`.
+    pub fn allow(
+        mut self,
+        name: &str,
+        origins: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        if let Some((_, allowed)) = self.commands.get_mut(name) {
+            allowed.extend(origins.into_iter().map(Into::into));
+        }
+        self
+    }
+
+    /// Check that the origin of the document calling `cef.invoke`, still loaded in `frame`, may
+    /// invoke the command of `request` and start it.
+    fn start(
+        &self,
+        frame: &CefFrame,
+        request: Invoke,
+    ) -> std::result::Result<CommandFuture, String> {
+        let (command, allowed) = self
+            .commands
+            .get(request.command.as_str())
+            .ok_or_else(|| format!("unknown command `{}`", request.command))?;
+        // The frame may have navigated since the call was made.
+        let origin = origin(&frame.get_url().unwrap_or_default().to_string());
+        if origin != request.origin {
+            return Err(format!(
+                "command `{}` was invoked from origin `{}`, but the frame is at `{origin}`",
+                command.name, request.origin
+            ));
+        }
+        if !allowed
+            .iter()
+            .any(|allowed| allowed == "*" || allowed.eq_ignore_ascii_case(&origin))
+        {
+            return Err(format!(
+                "command `{}` is not allowed for origin `{origin}`",
+                command.name
+            ));
+        }
+        if !request.args.is_object() && !request.args.is_null() {
+            return Err(format!("{}: arguments must be an object", command.name));
+        }
+        Ok((command.call)(CommandArgs(request.args)))
+    }
+}
+
+/// The lowercase `scheme://host[:port]` origin of `url`, without a default port.
+///
+/// `blob:` URLs have the origin of the URL they wrap, and URLs without a host, e.g. `about:blank`
+/// or `data:` URLs, the `null` origin.
+fn origin(url: &str) -> String {
+    const NULL: &str = "null";
+
+    let url = url.trim();
+    if let Some(inner) = url
+        .get(..5)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("blob:"))
+        .map(|_| &url[5..])
+    {
+        return origin(inner);
+    }
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return NULL.to_string();
+    };
+    // Rejects e.g. `data:text/html,https://app.example`.
+    let valid_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if !valid_scheme {
+        return NULL.to_string();
+    }
+    let scheme = scheme.to_ascii_lowercase();
+    let authority = rest.split(['/', '\\', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let host = host.to_ascii_lowercase();
+    let default_port = match scheme.as_str() {
+        "http" | "ws" => Some(":80"),
+        "https" | "wss" => Some(":443"),
+        _ => None,
+    };
+    let host = default_port
+        .and_then(|port| host.strip_suffix(port))
+        .unwrap_or(&host);
+    format!("{scheme}://{host}")
+}
+
+impl IpcRouter {
+    /// Serve `commands` to `cef.invoke`, see the [module docs](crate::Commands).
+    ///
+    /// Commands run on the UI thread, async ones being polled there until they complete.
+    pub fn with_commands(self, commands: Commands) -> Self {
+        self.on_request_deferred(
+            move |source, request: Invoke, responder: RpcResponder<serde_json::Value>| {
+                match commands.start(&source.frame, request) {
+                    Ok(future) => {
+                        _ = post_task(CefThreadId::TID_UI, move || {
+                            _ = spawn_local(CefThreadId::TID_UI, async move {
+                                _ = responder.respond(future.await);
+                            });
+                        });
+                    }
+                    Err(error) => _ = responder.respond(Err(error)),
+                }
+            },
+        )
+    }
+}
+
+/// Settles an invoke promise with a command result.
+struct JsonValue(serde_json::Value);
+
+impl IntoV8Value for JsonValue {
+    fn into_v8_value(self) -> V8Value {
+        crate::to_v8(&self.0).unwrap_or_else(|_| V8Value::undefined())
+    }
+}
+
+/// Install `cef.invoke(command, args)` on the global object of `context`, see the
+/// [module docs](crate::Commands).
+///
+/// Calls not answered within `timeout` reject their promise. Must be called on the renderer
+/// thread, e.g. from [CefRenderProcessHandler::on_context_created].
+///
+/// [CefRenderProcessHandler::on_context_created]: crate::CefRenderProcessHandler::on_context_created
+pub fn register_invoke(context: &CefV8Context, timeout: Duration) -> Result<()> {
+    in_context(context, || {
+        let object = object_at_path(context, "cef")?;
+        let handler =
+            V8Handler::new(move |_, _, _, args| invoke(args, timeout).map_err(anyhow::Error::msg));
+        object.set_value_bykey(
+            "invoke",
+            V8Value::function("invoke", handler),
+            CefV8PropertyAttribute::V8_PROPERTY_ATTRIBUTE_READONLY,
+        );
+        Ok(())
+    })
+}
+
+/// `cef.invoke(command, args)`, returning the promise of the result.
+fn invoke(args: &[V8Value], timeout: Duration) -> std::result::Result<V8Value, String> {
+    let command = args
+        .first()
+        .filter(|value| value.is_string().unwrap_or_default())
+        .and_then(|value| value.get_string_value())
+        .ok_or_else(|| "invoke: expected a command name".to_string())?
+        .to_string();
+    let args = match args.get(1) {
+        Some(value) if !value.is_undefined().unwrap_or(true) => {
+            crate::from_v8(value).map_err(|e| format!("invoke: {e}"))?
+        }
+        _ => serde_json::Value::Object(Default::default()),
+    };
+    let frame = CefV8Context::get_current()
+        .get_frame()
+        .ok_or_else(|| "invoke: no frame".to_string())?;
+    let origin = origin(&frame.get_url().unwrap_or_default().to_string());
+    let (promise, handle) = PromiseHandle::new().map_err(|e| format!("invoke: {e}"))?;
+    let request = Invoke {
+        command,
+        args,
+        origin,
+    };
+    frame.call_ipc_with(CefProcessId::PID_BROWSER, &request, timeout, |result| {
+        let result = result.map(JsonValue).map_err(|e| match e {
+            Error::RpcFailed(message) => message,
+            e => e.to_string(),
+        });
+        _ = handle.settle(result);
+    });
+    Ok(promise)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::{Context, Poll, Waker};
+
+    use serde::Deserialize;
+
+    use super::{origin, CommandArgs};
+
+    #[derive(Deserialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[crate::command(origins = ["*"])]
+    fn norm(p: Point) -> i32 {
+        p.x.abs() + p.y.abs()
+    }
+
+    #[test]
+    fn custom_argument_types() {
+        let args = CommandArgs(serde_json::json!({ "p": { "x": 3, "y": -4 } }));
+        let mut future = (norm::command().call)(args);
+        let result = future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()));
+        assert_eq!(result, Poll::Ready(Ok(serde_json::json!(7))));
+    }
+
+    #[test]
+    fn origin_of_urls() {
+        assert_eq!(
+            origin("https://app.example/index.html?a#b"),
+            "https://app.example"
+        );
+        assert_eq!(origin("https://app.example"), "https://app.example");
+        assert_eq!(origin("app://local/index.html"), "app://local");
+    }
+
+    #[test]
+    fn origin_ignores_userinfo() {
+        assert_eq!(origin("https://a@evil.example/"), "https://evil.example");
+        assert_eq!(
+            origin("https://app.example:pw@evil.example/"),
+            "https://evil.example"
+        );
+        assert_eq!(
+            origin("https://evil.example/@app.example"),
+            "https://evil.example"
+        );
+        assert_eq!(
+            origin("https://evil.example\\@app.example/"),
+            "https://evil.example"
+        );
+    }
+
+    #[test]
+    fn origin_keeps_other_ports() {
+        assert_eq!(origin("http://localhost:8080/"), "http://localhost:8080");
+        assert_eq!(origin("https://app.example:443/"), "https://app.example");
+        assert_eq!(origin("http://app.example:80"), "http://app.example");
+        assert_eq!(origin("https://app.example:80/"), "https://app.example:80");
+        assert_eq!(origin("http://[::1]:8080/"), "http://[::1]:8080");
+    }
+
+    #[test]
+    fn origin_is_lowercase() {
+        assert_eq!(origin("HTTPS://App.Example/Path"), "https://app.example");
+    }
+
+    #[test]
+    fn origin_of_special_urls() {
+        assert_eq!(origin("file:///home/user/index.html"), "file://");
+        assert_eq!(origin("about:blank"), "null");
+        assert_eq!(origin(""), "null");
+        assert_eq!(origin("data:text/html,<p>hi</p>"), "null");
+        assert_eq!(origin("data:text/html,https://app.example"), "null");
+        assert_eq!(
+            origin("blob:https://app.example/0b8e1c"),
+            "https://app.example"
+        );
+        assert_eq!(origin("BLOB:null/0b8e1c"), "null");
+    }
+}
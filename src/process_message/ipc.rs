@@ -0,0 +1,170 @@
+//! Typed IPC messages
+//!
+//! An [IpcMessage] is a serde type sent as a [CefProcessMessage] named after
+//! [IpcMessage::NAME], whose argument list holds the message converted to a single
+//! [CefValue](crate::CefValue). An [IpcRouter] decodes received messages and dispatches them
+//! by name, from either [CefClient::on_process_message_received] in the browser process or
+//! [CefRenderProcessHandler::on_process_message_received] in the renderer process.
+//!
+//! ```no_run
+//! use cef::{CefBrowser, CefFrame, CefProcessId, CefProcessMessage, IpcMessage, IpcRouter};
+//!
+//! #[derive(serde::Serialize, serde::Deserialize, IpcMessage)]
+//! #[ipc(name = "app.title")]
+//! struct TitleChanged {
+//!     title: String,
+//! }
+//!
+//! // Renderer process
+//! fn notify(frame: &CefFrame, title: String) -> cef::Result<()> {
+//!     frame.send_ipc_message(CefProcessId::PID_BROWSER, &TitleChanged { title })
+//! }
+//!
+//! // Browser process, with `dispatch` called from `CefClient::on_process_message_received`
+//! fn router() -> IpcRouter {
+//!     IpcRouter::new().on(|_source, message: TitleChanged| println!("{}", message.title))
+//! }
+//!
+//! fn dispatch(
+//!     router: &IpcRouter,
+//!     browser: CefBrowser,
+//!     frame: CefFrame,
+//!     source_process: CefProcessId,
+//!     message: CefProcessMessage,
+//! ) -> bool {
+//!     router.dispatch(browser, frame, source_process, &message).is_ok()
+//! }
+//! ```
+//!
+//! [CefClient::on_process_message_received]: crate::CefClient::on_process_message_received
+//! [CefRenderProcessHandler::on_process_message_received]: crate::CefRenderProcessHandler::on_process_message_received
+
+use std::collections::HashMap;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::CefProcessMessage;
+use crate::{prelude::*, CefBrowser, CefFrame, CefProcessId, CefValue};
+
+/// A message sent between processes, see the [module docs](self).
+///
+/// Usually derived with `#[derive(IpcMessage)]`, the name defaulting to the type name.
+pub trait IpcMessage: Serialize + DeserializeOwned {
+    /// Name of the process messages carrying this message.
+    const NAME: &'static str;
+
+    /// Encode this message into a new process message.
+    fn to_process_message(&self) -> Result<CefProcessMessage> {
+        let value = CefValue::from_json(&serde_json::to_value(self)?)?;
+        let message = CefProcessMessage::create(Self::NAME)?;
+        let arguments = message.get_argument_list().ok_or(Error::NullPtr)?;
+        if !arguments.set_value(0, value).unwrap_or_default() {
+            return Err(Error::NullPtr);
+        }
+        Ok(message)
+    }
+
+    /// Decode `message`, which must be named [IpcMessage::NAME].
+    fn from_process_message(message: &CefProcessMessage) -> Result<Self> {
+        let name = message.get_name().unwrap_or_default().to_string();
+        if name != Self::NAME {
+            return Err(Error::UnknownIpcMessage(name));
+        }
+        let malformed = |error: String| Error::MalformedIpcMessage {
+            name: name.clone(),
+            error,
+        };
+        let value = message
+            .get_argument_list()
+            .and_then(|arguments| arguments.get_value(0))
+            .ok_or_else(|| malformed("missing argument".to_string()))?;
+        let json = value.to_json().map_err(|e| malformed(e.to_string()))?;
+        serde_json::from_value(json).map_err(|e| malformed(e.to_string()))
+    }
+}
+
+impl CefFrame {
+    /// Send `message` to `target_process`, see [CefFrame::send_process_message].
+    pub fn send_ipc_message<M: IpcMessage>(
+        &self,
+        target_process: CefProcessId,
+        message: &M,
+    ) -> Result<()> {
+        let message = message.to_process_message()?;
+        self.send_process_message(target_process, message)
+            .ok_or(Error::NullPtr)
+    }
+}
+
+/// Where an [IpcMessage] was received from.
+#[derive(Debug, Clone)]
+pub struct IpcSource {
+    pub browser: CefBrowser,
+    pub frame: CefFrame,
+    pub process: CefProcessId,
+}
+
+type IpcHandler = Box<dyn Fn(IpcSource, &CefProcessMessage) -> Result<()> + Send + Sync>;
+
+/// Dispatches received process messages to the handler of their [IpcMessage] type.
+#[derive(Default)]
+pub struct IpcRouter {
+    handlers: HashMap<&'static str, IpcHandler>,
+}
+
+impl IpcRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `handler` with the messages of type `M`, replacing the previous handler of `M`.
+    pub fn on<M: IpcMessage>(self, handler: impl Fn(IpcSource, M) + Send + Sync + 'static) -> Self {
+        self.with_handler(M::NAME, move |source, message| {
+            handler(source, M::from_process_message(message)?);
+            Ok(())
+        })
+    }
+
+    /// Call `handler` with the messages named `name`.
+    pub(super) fn with_handler(
+        mut self,
+        name: &'static str,
+        handler: impl Fn(IpcSource, &CefProcessMessage) -> Result<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.handlers.insert(name, Box::new(handler));
+        self
+    }
+
+    /// `true` if a handler is registered for messages named `name`.
+    pub fn handles(&self, name: &str) -> bool {
+        self.handlers.contains_key(name)
+    }
+
+    /// Decode `message` and call the handler of its type. Responses to calls made with
+    /// [CefFrame::call_ipc] are routed to their call.
+    ///
+    /// Fails with [Error::UnknownIpcMessage] if no handler matches its name, and with
+    /// [Error::MalformedIpcMessage] if it can't be decoded, in which case no handler is called.
+    pub fn dispatch(
+        &self,
+        browser: CefBrowser,
+        frame: CefFrame,
+        source_process: CefProcessId,
+        message: &CefProcessMessage,
+    ) -> Result<()> {
+        let name = message.get_name().unwrap_or_default().to_string();
+        if name == super::rpc::RESPONSE_NAME {
+            return super::rpc::response_received(message);
+        }
+        let handler = self
+            .handlers
+            .get(name.as_str())
+            .ok_or(Error::UnknownIpcMessage(name))?;
+        let source = IpcSource {
+            browser,
+            frame,
+            process: source_process,
+        };
+        handler(source, message)
+    }
+}
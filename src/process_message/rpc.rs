@@ -0,0 +1,380 @@
+//! Request/response calls between processes
+//!
+//! An [IpcRequest] is an [IpcMessage] expecting a response. [CefFrame::call_ipc] sends it
+//! with a correlation id, and the receiving process answers through an [IpcRouter] handler
+//! registered with [IpcRouter::on_request]. The response is routed back to the call by
+//! [IpcRouter::dispatch] in the calling process.
+//!
+//! A call fails with [Error::RpcTimeout] once its timeout expires, and with
+//! [Error::RpcCancelled] when its browser closes, its frame is detached or its renderer
+//! process terminates, and in the renderer process when the context of its frame is released.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use cef::{CefFrame, CefProcessId, IpcMessage, IpcRequest, IpcRouter};
+//!
+//! #[derive(serde::Serialize, serde::Deserialize, IpcMessage)]
+//! struct ReadSetting {
+//!     key: String,
+//! }
+//!
+//! impl IpcRequest for ReadSetting {
+//!     type Response = Option<String>;
+//! }
+//!
+//! // Browser process
+//! fn router() -> IpcRouter {
+//!     IpcRouter::new().on_request(|_source, request: ReadSetting| Ok(std::env::var(request.key).ok()))
+//! }
+//!
+//! // Renderer process
+//! async fn read_setting(frame: &CefFrame, key: &str) -> cef::Result<Option<String>> {
+//!     let request = ReadSetting { key: key.to_string() };
+//!     frame
+//!         .call_ipc(CefProcessId::PID_BROWSER, &request, Duration::from_secs(5))
+//!         .await
+//! }
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{CefProcessMessage, IpcMessage, IpcRouter, IpcSource};
+use crate::{
+    oneshot,
+    prelude::*,
+    task::{currently_on, post_delayed_task_deferred, post_task},
+    CefBrowser, CefFrame, CefProcessId, CefThreadId, CefValue,
+};
+
+/// Name of the process messages carrying responses.
+pub(super) const RESPONSE_NAME: &str = "cef.rpc.response";
+
+/// An [IpcMessage] answered with a [IpcRequest::Response], see the [module docs](self).
+pub trait IpcRequest: IpcMessage {
+    type Response: Serialize + DeserializeOwned;
+}
+
+/// Receives the outcome of a call, with the response still encoded.
+type Completion = Box<dyn FnOnce(Result<serde_json::Value>) + Send>;
+
+struct PendingCall {
+    browser_id: i32,
+    frame_id: String,
+    complete: Completion,
+}
+
+/// Calls waiting for their response, in this process.
+static PENDING: Mutex<BTreeMap<u64, PendingCall>> = Mutex::new(BTreeMap::new());
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+fn frame_id(frame: &CefFrame) -> String {
+    frame.get_identifier().unwrap_or_default().to_string()
+}
+
+/// Complete call `id` with `result`, unless it already completed.
+fn complete_call(id: u64, result: Result<serde_json::Value>) {
+    let call = PENDING.lock().unwrap().remove(&id);
+    if let Some(call) = call {
+        (call.complete)(result);
+    }
+}
+
+/// Fail the pending calls matching `cancel` with [Error::RpcCancelled].
+fn cancel_calls(cancel: impl Fn(&PendingCall) -> bool) {
+    let cancelled: Vec<PendingCall> = {
+        let mut pending = PENDING.lock().unwrap();
+        let ids: Vec<u64> = pending
+            .iter()
+            .filter(|(_, call)| cancel(call))
+            .map(|(id, _)| *id)
+            .collect();
+        ids.iter().filter_map(|id| pending.remove(id)).collect()
+    };
+    for call in cancelled {
+        (call.complete)(Err(Error::RpcCancelled));
+    }
+}
+
+/// Cancel the pending calls made from `frame`. Called when it's detached.
+pub fn cancel_rpc_calls_for_frame(frame: &CefFrame) {
+    let frame_id = frame_id(frame);
+    cancel_calls(|call| call.frame_id == frame_id);
+}
+
+/// Cancel the pending calls made from the frames of `browser`. Called when the browser closes
+/// and when its renderer process terminates.
+pub fn cancel_rpc_calls_for_browser(browser: &CefBrowser) {
+    let browser_id = browser.get_identifier().unwrap_or_default();
+    cancel_calls(|call| call.browser_id == browser_id);
+}
+
+/// Send `request` as call `id` and register `complete` for its response.
+fn start_call<R: IpcRequest>(
+    frame: &CefFrame,
+    target_process: CefProcessId,
+    request: &R,
+    timeout: Duration,
+    complete: Completion,
+) -> u64 {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let message = match request_message(request, id) {
+        Ok(message) => message,
+        Err(e) => {
+            complete(Err(e));
+            return id;
+        }
+    };
+    let call = PendingCall {
+        browser_id: frame
+            .get_browser()
+            .and_then(|browser| browser.get_identifier())
+            .unwrap_or_default(),
+        frame_id: frame_id(frame),
+        complete,
+    };
+    PENDING.lock().unwrap().insert(id, call);
+
+    // Calls are made on the renderer thread in the renderer process.
+    let timer_thread = if currently_on(CefThreadId::TID_RENDERER) {
+        CefThreadId::TID_RENDERER
+    } else {
+        CefThreadId::TID_UI
+    };
+    let timer = move || complete_call(id, Err(Error::RpcTimeout));
+    if let Err(e) = post_delayed_task_deferred(timer_thread, timer, timeout) {
+        complete_call(id, Err(e));
+    } else if frame
+        .send_process_message(target_process, message)
+        .is_none()
+    {
+        complete_call(id, Err(Error::NullPtr));
+    }
+    id
+}
+
+/// Encode `request` along with its call id.
+fn request_message<R: IpcRequest>(request: &R, id: u64) -> Result<CefProcessMessage> {
+    let message = request.to_process_message()?;
+    let arguments = message.get_argument_list().ok_or(Error::NullPtr)?;
+    if !arguments
+        .set_string(1, CefString::from(id.to_string().as_str()))
+        .unwrap_or_default()
+    {
+        return Err(Error::NullPtr);
+    }
+    Ok(message)
+}
+
+/// Read the call id of a request or response, at `index` of its arguments.
+fn call_id(message: &CefProcessMessage, index: usize) -> Result<u64> {
+    let name = message.get_name().unwrap_or_default().to_string();
+    message
+        .get_argument_list()
+        .and_then(|arguments| arguments.get_string(index))
+        .and_then(|id| id.to_string().parse().ok())
+        .ok_or_else(|| Error::MalformedIpcMessage {
+            name,
+            error: "missing call id".to_string(),
+        })
+}
+
+/// Route a response message to its pending call.
+pub(super) fn response_received(message: &CefProcessMessage) -> Result<()> {
+    let id = call_id(message, 0)?;
+    let arguments = message.get_argument_list().ok_or(Error::NullPtr)?;
+    let result = if arguments.get_bool(1).unwrap_or_default() {
+        arguments
+            .get_value(2)
+            .ok_or(Error::NullPtr)
+            .and_then(|value| value.to_json())
+    } else {
+        let error = arguments.get_string(2).unwrap_or_default().to_string();
+        Err(Error::RpcFailed(error))
+    };
+    complete_call(id, result);
+    Ok(())
+}
+
+/// Send the response of call `id` to `process`, on the renderer thread if `renderer`.
+fn send_response(
+    frame: CefFrame,
+    process: CefProcessId,
+    renderer: bool,
+    id: u64,
+    result: std::result::Result<serde_json::Value, String>,
+) -> Result<()> {
+    let message = CefProcessMessage::create(RESPONSE_NAME)?;
+    let arguments = message.get_argument_list().ok_or(Error::NullPtr)?;
+    arguments.set_string(0, CefString::from(id.to_string().as_str()));
+    arguments.set_bool(1, result.is_ok());
+    match result {
+        Ok(json) => arguments.set_value(2, CefValue::from_json(&json)?),
+        Err(error) => arguments.set_string(2, CefString::from(error.as_str())),
+    };
+    // Frames can only be used on the renderer thread in the renderer process.
+    if renderer {
+        post_task(CefThreadId::TID_RENDERER, move || {
+            frame.send_process_message(process, message);
+        })
+    } else {
+        frame.send_process_message(process, message);
+        Ok(())
+    }
+}
+
+/// Answers one [IpcRequest], from any thread.
+///
+/// Dropping it without responding fails the call with [Error::RpcFailed].
+pub struct RpcResponder<T> {
+    frame: CefFrame,
+    process: CefProcessId,
+    renderer: bool,
+    id: u64,
+    responded: bool,
+    response: PhantomData<fn(T)>,
+}
+
+impl<T: Serialize> RpcResponder<T> {
+    /// Respond with `Ok` or fail the call with the `Err` message.
+    pub fn respond(mut self, result: std::result::Result<T, String>) -> Result<()> {
+        self.responded = true;
+        let result = match result {
+            Ok(response) => Ok(serde_json::to_value(response)?),
+            Err(error) => Err(error),
+        };
+        send_response(
+            self.frame.clone(),
+            self.process,
+            self.renderer,
+            self.id,
+            result,
+        )
+    }
+}
+
+impl<T> Drop for RpcResponder<T> {
+    fn drop(&mut self) {
+        if !self.responded {
+            let error = Err("request dropped without a response".to_string());
+            _ = send_response(
+                self.frame.clone(),
+                self.process,
+                self.renderer,
+                self.id,
+                error,
+            );
+        }
+    }
+}
+
+impl IpcRouter {
+    /// Answer the requests of type `R` with the result of `handler`, replacing the previous
+    /// handler of `R`.
+    pub fn on_request<R: IpcRequest>(
+        self,
+        handler: impl Fn(IpcSource, R) -> std::result::Result<R::Response, String>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.on_request_deferred(move |source, request: R, responder| {
+            _ = responder.respond(handler(source, request));
+        })
+    }
+
+    /// Call `handler` with the requests of type `R` and an [RpcResponder] answering them,
+    /// possibly later, replacing the previous handler of `R`.
+    pub fn on_request_deferred<R: IpcRequest>(
+        self,
+        handler: impl Fn(IpcSource, R, RpcResponder<R::Response>) + Send + Sync + 'static,
+    ) -> Self {
+        self.with_handler(R::NAME, move |source, message| {
+            let responder = RpcResponder {
+                frame: source.frame.clone(),
+                process: source.process,
+                renderer: currently_on(CefThreadId::TID_RENDERER),
+                id: call_id(message, 1)?,
+                responded: false,
+                response: PhantomData,
+            };
+            // A malformed request still gets an error response from the dropped responder.
+            let request = R::from_process_message(message)?;
+            handler(source, request, responder);
+            Ok(())
+        })
+    }
+}
+
+impl CefFrame {
+    /// Send `request` to `target_process` and wait for its response, see the
+    /// [module docs](crate::IpcRequest).
+    pub fn call_ipc<R: IpcRequest>(
+        &self,
+        target_process: CefProcessId,
+        request: &R,
+        timeout: Duration,
+    ) -> RpcCall<R::Response>
+    where
+        R::Response: Send + 'static,
+    {
+        let (sender, response) = oneshot::channel();
+        let complete: Completion = Box::new(move |result| sender.send(decode_response(result)));
+        let id = start_call(self, target_process, request, timeout, complete);
+        RpcCall { id, response }
+    }
+
+    /// Send `request` to `target_process` and call `callback` with its response, on the
+    /// thread receiving it.
+    pub fn call_ipc_with<R: IpcRequest>(
+        &self,
+        target_process: CefProcessId,
+        request: &R,
+        timeout: Duration,
+        callback: impl FnOnce(Result<R::Response>) + Send + 'static,
+    ) {
+        let complete: Completion = Box::new(move |result| callback(decode_response(result)));
+        start_call(self, target_process, request, timeout, complete);
+    }
+}
+
+fn decode_response<T: DeserializeOwned>(result: Result<serde_json::Value>) -> Result<T> {
+    serde_json::from_value(result?).map_err(|e| Error::MalformedIpcMessage {
+        name: RESPONSE_NAME.to_string(),
+        error: e.to_string(),
+    })
+}
+
+/// The future returned by [CefFrame::call_ipc]. Dropping it cancels the call.
+pub struct RpcCall<T> {
+    id: u64,
+    response: oneshot::Receiver<Result<T>>,
+}
+
+impl<T> Future for RpcCall<T> {
+    type Output = Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.response).poll(cx)
+    }
+}
+
+impl<T> Drop for RpcCall<T> {
+    fn drop(&mut self) {
+        PENDING.lock().unwrap().remove(&self.id);
+    }
+}
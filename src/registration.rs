@@ -0,0 +1,8 @@
+use crate::prelude::*;
+
+/// See [cef_registration_t] for more docs.
+///
+/// The associated observer stays registered until the last reference is dropped.
+#[derive(Debug, Clone)]
+#[wrapper]
+pub struct CefRegistration(cef_registration_t);
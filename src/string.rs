@@ -12,7 +12,6 @@ use cef_sys::{
     cef_string_list_t, cef_string_map_t, cef_string_userfree_utf16_t, cef_string_utf16_t,
 };
 use std::collections::HashMap;
-use std::ptr::null_mut;
 use widestring::U16CString;
 
 /// Helper type to deal with Cef string. It's essentially an UTF-16 C string.
@@ -89,12 +88,13 @@ pub unsafe fn parse_string_list(ptr: cef_string_list_t) -> Vec<CefString> {
     let count = cef_sys::cef_string_list_size(ptr);
     let mut res = Vec::with_capacity(count);
     for i in 0..count {
-        let value = null_mut();
-        if cef_sys::cef_string_list_value(ptr, i, value) > 0 {
-            if let Some(v) = CefString::from_raw(value) {
+        let mut value: cef_string_utf16_t = std::mem::zeroed();
+        if cef_sys::cef_string_list_value(ptr, i, &mut value) > 0 {
+            if let Some(v) = CefString::from_raw(&value) {
                 res.push(v)
             }
         }
+        cef_sys::cef_string_utf16_clear(&mut value);
     }
     res
 }
@@ -104,13 +104,17 @@ pub unsafe fn parse_string_map(ptr: cef_string_map_t) -> HashMap<String, String>
     let count = cef_sys::cef_string_map_size(ptr);
     let mut res = HashMap::with_capacity(count);
     for i in 0..count {
-        let key = null_mut();
-        let value = null_mut();
-        cef_sys::cef_string_map_key(ptr, i, key);
-        cef_sys::cef_string_map_value(ptr, i, value);
-
-        CefString::from_raw(key)
-            .map(|k| CefString::from_raw(value).map(|v| res.insert(k.to_string(), v.to_string())));
+        let mut key: cef_string_utf16_t = std::mem::zeroed();
+        let mut value: cef_string_utf16_t = std::mem::zeroed();
+        if cef_sys::cef_string_map_key(ptr, i, &mut key) == 1
+            && cef_sys::cef_string_map_value(ptr, i, &mut value) == 1
+        {
+            CefString::from_raw(&key).map(|k| {
+                CefString::from_raw(&value).map(|v| res.insert(k.to_string(), v.to_string()))
+            });
+        }
+        cef_sys::cef_string_utf16_clear(&mut key);
+        cef_sys::cef_string_utf16_clear(&mut value);
     }
     res
 }
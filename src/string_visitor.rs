@@ -0,0 +1,90 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll},
+};
+
+use crate::{oneshot, prelude::*};
+
+/// See [cef_string_visitor_t]
+pub trait CefStringVisitor: Sized {
+    /// See [cef_string_visitor_t::visit]
+    fn visit(&self, string: CefString);
+
+    #[doc(hidden)]
+    fn into_raw(self) -> *mut cef_string_visitor_t {
+        unsafe extern "C" fn visit<T: CefStringVisitor>(
+            self_: *mut _cef_string_visitor_t,
+            string: *const cef_string_t,
+        ) {
+            let object: &crate::rc::RcImpl<_, T> = crate::rc::RcImpl::get(self_);
+            let string = unsafe { CefString::from_raw(string) }.unwrap_or_default();
+            object.interface.visit(string);
+        }
+
+        let mut object: cef_string_visitor_t = unsafe { std::mem::zeroed() };
+        object.visit = Some(visit::<Self>);
+        crate::rc::RcImpl::new(object, self).cast()
+    }
+}
+
+/// [CefStringVisitor] calling a closure with the visited string.
+///
+/// The closure is only called for the first visit.
+pub struct StringVisitorFn<F: FnOnce(CefString)> {
+    func: Mutex<Option<F>>,
+}
+
+impl<F: FnOnce(CefString)> StringVisitorFn<F> {
+    pub fn new(func: F) -> Self {
+        Self {
+            func: Mutex::new(Some(func)),
+        }
+    }
+}
+
+impl<F: FnOnce(CefString)> CefStringVisitor for StringVisitorFn<F> {
+    fn visit(&self, string: CefString) {
+        if let Some(func) = self.func.lock().unwrap().take() {
+            func(string);
+        }
+    }
+}
+
+/// [CefStringVisitor] completing a [StringFuture].
+pub(crate) struct StringSender(oneshot::Sender<String>);
+
+impl CefStringVisitor for StringSender {
+    fn visit(&self, string: CefString) {
+        self.0.send(string.to_string());
+    }
+}
+
+impl Drop for StringSender {
+    fn drop(&mut self) {
+        self.0.send(String::new());
+    }
+}
+
+/// Future resolving with the string passed to a [CefStringVisitor], e.g. by
+/// [CefFrame::get_source_async](crate::CefFrame::get_source_async).
+///
+/// Resolves with an empty string if the visitor is released without being called.
+pub struct StringFuture(oneshot::Receiver<String>);
+
+impl StringFuture {
+    /// Create a future and the visitor completing it.
+    pub(crate) fn new() -> (Self, StringSender) {
+        let (sender, receiver) = oneshot::channel();
+        (Self(receiver), StringSender(sender))
+    }
+}
+
+impl Future for StringFuture {
+    type Output = String;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
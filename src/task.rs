@@ -1,4 +1,15 @@
-use std::time::Duration;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Wake, Waker},
+    time::Duration,
+};
 
 use crate::prelude::*;
 use crate::rc::RcImpl;
@@ -42,6 +53,36 @@ pub fn post_delayed_task<F: FnOnce()>(
     }
 }
 
+/// Post `task` to `id` even if called on that thread, so it runs after the current task.
+pub(crate) fn post_task_deferred<F: FnOnce()>(
+    id: CefThreadId,
+    task: F,
+) -> crate::error::Result<()> {
+    let task = TaskWrapper { func: Some(task) };
+    let ret = unsafe { cef_post_task(id, task.into_raw()) };
+    if ret > 0 {
+        Ok(())
+    } else {
+        Err(Error::CannotPostTask(id as _))
+    }
+}
+
+/// Post `task` to `id` after `delay`, even if called on that thread.
+#[cfg(feature = "serde")]
+pub(crate) fn post_delayed_task_deferred<F: FnOnce()>(
+    id: CefThreadId,
+    task: F,
+    delay: Duration,
+) -> crate::error::Result<()> {
+    let task = TaskWrapper { func: Some(task) };
+    let ret = unsafe { cef_post_delayed_task(id, task.into_raw(), delay.as_millis() as _) };
+    if ret > 0 {
+        Ok(())
+    } else {
+        Err(Error::CannotPostTask(id as _))
+    }
+}
+
 pub struct TaskWrapper<F: FnOnce()> {
     func: Option<F>,
 }
@@ -60,3 +101,81 @@ impl<F: FnOnce()> TaskWrapper<F> {
         RcImpl::new(object, self).cast()
     }
 }
+
+/// A future driven by [spawn_local].
+type LocalTask = Pin<Box<dyn Future<Output = ()>>>;
+
+thread_local! {
+    /// Futures spawned on this thread, `None` while being polled.
+    static TASKS: RefCell<HashMap<u64, Option<LocalTask>>> = RefCell::new(HashMap::new());
+}
+
+static NEXT_TASK_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Identifies a future spawned with [spawn_local].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TaskId(u64);
+
+/// Run `future` to completion on thread `id`, which must be the current thread.
+///
+/// The future is first polled in a later task, then in a new task of `id` whenever it's woken.
+/// This is a minimal executor without any I/O reactor or timer.
+pub(crate) fn spawn_local(
+    id: CefThreadId,
+    future: impl Future<Output = ()> + 'static,
+) -> crate::error::Result<TaskId> {
+    if !currently_on(id) {
+        return Err(Error::WrongThread(id as _));
+    }
+    let task = NEXT_TASK_ID.fetch_add(1, Ordering::Relaxed);
+    TASKS.with_borrow_mut(|tasks| tasks.insert(task, Some(Box::pin(future))));
+    Waker::from(Arc::new(TaskWaker { thread: id, task })).wake();
+    Ok(TaskId(task))
+}
+
+/// Drop the future spawned as `task`, if it hasn't completed yet. Must be called on the thread
+/// the future was spawned on.
+pub(crate) fn cancel_local(task: TaskId) {
+    // Dropped outside of the borrow, the future may spawn or cancel others when dropped.
+    let future = TASKS.with_borrow_mut(|tasks| tasks.remove(&task.0));
+    drop(future);
+}
+
+/// Wakes a future of [TASKS] by polling it in a new task of its thread.
+struct TaskWaker {
+    thread: CefThreadId,
+    task: u64,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        let (thread, task) = (self.thread, self.task);
+        // Deferred, so a future waking itself while being polled is polled again afterwards.
+        _ = post_task_deferred(thread, move || poll_local(thread, task));
+    }
+}
+
+fn poll_local(thread: CefThreadId, task: u64) {
+    // Take the future out while polling, it may spawn or cancel other futures.
+    let Some(mut future) = TASKS.with_borrow_mut(|tasks| tasks.get_mut(&task)?.take()) else {
+        return;
+    };
+    let waker = Waker::from(Arc::new(TaskWaker { thread, task }));
+    if future
+        .as_mut()
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready()
+    {
+        TASKS.with_borrow_mut(|tasks| tasks.remove(&task));
+        return;
+    }
+    // The future is dropped if it was cancelled while being polled.
+    let cancelled = TASKS.with_borrow_mut(|tasks| match tasks.get_mut(&task) {
+        Some(slot) => {
+            *slot = Some(future);
+            None
+        }
+        None => Some(future),
+    });
+    drop(cancelled);
+}
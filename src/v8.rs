@@ -1,11 +1,42 @@
 use std::{
-    collections::HashMap,
+    any::Any,
+    collections::BTreeSet,
     panic::AssertUnwindSafe,
-    ptr::null_mut,
-    sync::{Arc, Mutex},
+    ptr::{null_mut, NonNull},
+    sync::Mutex,
 };
 
-use crate::{prelude::*, rc::RcImpl};
+use crate::{
+    prelude::*,
+    rc::{Rc, RcImpl, RefGuard},
+};
+
+mod array_buffer;
+mod callback;
+mod extension;
+mod js_class;
+mod js_function;
+mod promise;
+#[cfg(feature = "serde")]
+mod serde;
+mod typescript;
+#[cfg(feature = "serde")]
+pub use self::serde::*;
+pub use callback::*;
+pub use extension::*;
+pub use js_class::*;
+pub use js_function::*;
+pub use promise::*;
+pub use typescript::*;
+
+/// Drop the Rust state tied to `context`.
+///
+/// Called before [CefRenderProcessHandler::on_context_released](crate::CefRenderProcessHandler::on_context_released).
+pub(crate) fn context_released(context: &CefV8Context) {
+    callback::context_released(context);
+    js_class::context_released(context);
+    promise::context_released(context);
+}
 
 /// See [cef_v8context_t] for more documentation.
 #[derive(Debug, Clone)]
@@ -65,22 +96,87 @@ impl CefV8Context {
         /// See [cef_v8context_t::exit].
         fn exit(&self) -> bool;
 
-        /// See [cef_v8context_t::eval].
-        fn eval(&self,
-            code: &str,
-            script_url: &str,
-            start_line: i32,
-            retval: &mut V8Value,
-            exception: &mut V8Excepction,
-        ) -> bool {
-             eval.map(|f| unsafe {
-                let code = CefString::from(code);
-                let script_url = CefString::from(script_url);
-                f(self.get_this(), &code.as_raw(), &script_url.as_raw(), start_line as _,
-                    &mut retval.clone().into_raw(), &mut exception.clone().into_raw() ) == 1
-            })
+    }
+
+    /// See [cef_v8context_t::eval].
+    ///
+    /// Evaluate `code` in this context, with `script_url` and `start_line` used for the
+    /// location of errors. An exception thrown by the script is returned as the error, carrying
+    /// its message and location. If the context can't evaluate scripts at all, the returned
+    /// exception is empty, see [V8Exception].
+    pub fn eval(
+        &self,
+        code: &str,
+        script_url: &str,
+        start_line: i32,
+    ) -> std::result::Result<V8Value, V8Exception> {
+        let Some(eval) = self.0.as_ref().and_then(|inner| inner.eval) else {
+            return Err(V8Exception::from(null_mut()));
+        };
+        let code = CefString::from(code);
+        let script_url = CefString::from(script_url);
+        let mut retval = null_mut();
+        let mut exception = null_mut();
+        let ok = unsafe {
+            eval(
+                self.get_this(),
+                &code.as_raw(),
+                &script_url.as_raw(),
+                start_line as _,
+                &mut retval,
+                &mut exception,
+            )
+        };
+        let retval = V8Value::from(retval);
+        if ok == 1 {
+            Ok(retval)
+        } else {
+            Err(V8Exception::from(exception))
+        }
+    }
+}
+
+/// Enters a [CefV8Context] on creation and exits it when dropped.
+///
+/// ```no_run
+/// # fn f(context: &cef::CefV8Context) -> cef::Result<()> {
+/// let _scope = context.scope()?;
+/// let value = context.eval("1 + 1", "", 0)?;
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "the context is exited as soon as the scope is dropped"]
+pub struct V8ContextScope {
+    context: CefV8Context,
+}
+
+impl V8ContextScope {
+    /// Enter `context`, failing if it's invalid or can't be entered.
+    pub fn new(context: &CefV8Context) -> Result<Self> {
+        if !context.is_valid().unwrap_or_default() || !context.enter().unwrap_or_default() {
+            return Err(Error::CannotEnterV8Context);
         }
+        Ok(Self {
+            context: context.clone(),
+        })
+    }
+
+    /// The entered context.
+    pub fn context(&self) -> &CefV8Context {
+        &self.context
+    }
+}
+
+impl Drop for V8ContextScope {
+    fn drop(&mut self) {
+        self.context.exit();
+    }
+}
 
+impl CefV8Context {
+    /// Enter this context until the returned scope is dropped, see [V8ContextScope].
+    pub fn scope(&self) -> Result<V8ContextScope> {
+        V8ContextScope::new(self)
     }
 }
 
@@ -131,7 +227,11 @@ impl V8Value {
     }
 
     /// See [cef_v8value_create_object].
-    pub fn object(accessor: V8Accessor, interceptor: V8Interceptor) -> Self {
+    ///
+    /// Pass `()` as `accessor` or `interceptor` to go without one, e.g. `V8Value::object((), ())`
+    /// creates a plain object. Properties served by `accessor` are registered with
+    /// [V8Value::set_value_byaccessor].
+    pub fn object(accessor: impl V8Accessor, interceptor: impl V8Interceptor) -> Self {
         unsafe {
             Self::from(cef_v8value_create_object(
                 accessor.into_raw(),
@@ -146,28 +246,10 @@ impl V8Value {
     }
 
     /// See [cef_v8value_create_array_buffer].
+    ///
+    /// Create a zeroed ArrayBuffer of `length` bytes, see [V8Value::array_buffer_from_vec].
     pub fn array_buffer(length: usize) -> Self {
-        let mut buffer = vec![0; length];
-        unsafe {
-            let mut callback: cef_v8array_buffer_release_callback_t = std::mem::zeroed();
-            callback.release_buffer = Some(release_buffer);
-            let callback = RcImpl::new(callback, V8ArrayBufferReleaseCallback);
-
-            extern "C" fn release_buffer(
-                _: *mut _cef_v8array_buffer_release_callback_t,
-                buffer: *mut core::ffi::c_void,
-            ) {
-                _ = Box::from(buffer);
-            }
-
-            let v = cef_v8value_create_array_buffer(
-                buffer.as_mut_ptr().cast(),
-                buffer.len() as _,
-                callback.cast(),
-            );
-            core::mem::forget(buffer);
-            Self::from(v)
-        }
+        Self::array_buffer_from_vec(vec![0; length])
     }
 
     /// See [cef_v8value_create_array_buffer_with_copy].
@@ -193,6 +275,7 @@ impl V8Value {
         }
     }
 
+    #[deprecated(note = "the contents may be released while borrowed, use `array_buffer_to_vec`")]
     pub fn get_array_buffer(&self) -> &[u8] {
         if !self.is_valid().unwrap_or_default() {
             return &[];
@@ -317,11 +400,11 @@ impl V8Value {
         fn has_exception(&self) -> bool;
 
         /// See [cef_v8value_t::get_exception].
-        fn get_exception(&self) -> V8Excepction {
+        fn get_exception(&self) -> V8Exception {
             if !self.is_valid().unwrap_or_default() { return None; }
              get_exception.and_then(|f| unsafe {
                 let v = f(self.get_this());
-                if v.is_null() { None } else  {V8Excepction::from(v).into()}
+                if v.is_null() { None } else  {V8Exception::from(v).into()}
             })
         }
 
@@ -343,12 +426,36 @@ impl V8Value {
              set_rethrow_exceptions.map(|f| unsafe { f(self.get_this(), rethrow as _); })
         }
 
+        /// See [cef_v8value_t::get_keys].
+        fn get_keys(&self) -> Vec<CefString> {
+            if !self.is_valid().unwrap_or_default() { return None; }
+            get_keys.and_then(|f| unsafe {
+                let list = cef_string_list_alloc();
+                let ok = f(self.get_this(), list) == 1;
+                let keys = crate::string::parse_string_list(list);
+                cef_string_list_free(list);
+                ok.then_some(keys)
+            })
+        }
+
         /// See [cef_v8value_t::has_value_bykey].
         fn has_value_bykey(&self, key: &str) -> bool {
             if !self.is_valid().unwrap_or_default() { return None; }
              has_value_bykey.map(|f| unsafe { f(self.get_this(), &CefString::from(key).as_raw()) == 1 })
         }
 
+        /// See [cef_v8value_t::delete_value_bykey].
+        fn delete_value_bykey(&self, key: &str) -> bool {
+            if !self.is_valid().unwrap_or_default() { return None; }
+            delete_value_bykey.map(|f| unsafe { f(self.get_this(), &CefString::from(key).as_raw()) == 1 })
+        }
+
+        /// See [cef_v8value_t::delete_value_byindex].
+        fn delete_value_byindex(&self, index: usize) -> bool {
+            if !self.is_valid().unwrap_or_default() { return None; }
+            delete_value_byindex.map(|f| unsafe { f(self.get_this(), index as _) == 1 })
+        }
+
         /// See [cef_v8value_t::get_value_bykey].
         fn get_value_bykey(&self, key: &str) -> Self {
             if !self.is_valid().unwrap_or_default() { return None; }
@@ -387,6 +494,20 @@ impl V8Value {
              set_value_byaccessor.map(|f| unsafe { f(self.get_this(), &CefString::from(key).as_raw(),  attribute as _); })
         }
 
+        /// See [cef_v8value_t::get_function_handler]
+        ///
+        /// `None` for functions not created by [V8Value::function].
+        fn get_function_handler(&self) -> V8FunctionHandler {
+            if !self.is_valid().unwrap_or_default() { return None; }
+            get_function_handler.and_then(|f| unsafe {
+                let handler = RefGuard::from_raw(NonNull::new(f(self.get_this()))?);
+                // Handlers of other CEF users in the process, e.g. C++ code, have another
+                // `execute`.
+                let execute = handler.execute? as *const ();
+                (execute == execute_handler as *const ()).then_some(V8FunctionHandler(handler))
+            })
+        }
+
         /// See [cef_v8value_t::execute_function].
         fn execute_function(&self, object: Option<V8Value>, args: Vec<V8Value>) -> Self {
             if !self.is_valid().unwrap_or_default() { return None; }
@@ -419,6 +540,30 @@ impl V8Value {
              reject_promise.map(|f| unsafe { f(self.get_this(), &CefString::from(err).as_raw()) == 1 })
         }
 
+        /// See [cef_v8value_t::get_externally_allocated_memory]
+        fn get_externally_allocated_memory(&self) -> i32 {
+            if !self.is_valid().unwrap_or_default() { return None; }
+            get_externally_allocated_memory.map(|f| unsafe { f(self.get_this()) })
+        }
+
+        /// See [cef_v8value_t::adjust_externally_allocated_memory]
+        ///
+        /// Tell V8 that `change_in_bytes` more (or less) memory is kept alive by the user data of
+        /// this object, and return the new total.
+        fn adjust_externally_allocated_memory(&self, change_in_bytes: i32) -> i32 {
+            if !self.is_valid().unwrap_or_default() { return None; }
+            adjust_externally_allocated_memory.map(|f| unsafe { f(self.get_this(), change_in_bytes) })
+        }
+
+        /// See [cef_v8value_t::neuter_array_buffer]
+        ///
+        /// Detach the contents of this ArrayBuffer, which becomes empty. Its release callback is
+        /// still called when V8 collects it.
+        fn neuter_array_buffer(&self) -> bool {
+            if !self.is_valid().unwrap_or_default() { return None; }
+            neuter_array_buffer.map(|f| unsafe { f(self.get_this()) == 1 })
+        }
+
         /// See [cef_v8value_t::get_array_buffer_byte_length]
         fn get_array_buffer_byte_length(&self) -> usize {
             if !self.is_valid().unwrap_or_default() { return None; }
@@ -430,12 +575,27 @@ impl V8Value {
         /// See [cef_v8value_t::get_array_buffer_data]
         fn get_array_buffer_data(&self) -> *mut u8 {
             if !self.is_valid().unwrap_or_default() { return None; }
-            let Some(len) = self.get_array_buffer_byte_length() else { return None;};
             get_array_buffer_data.map(|f|unsafe {
                 f(self.get_this()).cast()
             })
         }
 
+        /// See [cef_v8value_t::set_user_data]
+        ///
+        /// `data` is dropped when it's replaced or when V8 collects the value.
+        fn set_user_data<T: Any>(&self, data: T) -> bool {
+            if !self.is_valid().unwrap_or_default() { return None; }
+            set_user_data.map(|f| unsafe { f(self.get_this(), UserData::into_raw(data)) == 1 })
+        }
+
+        /// See [cef_v8value_t::get_user_data]
+        ///
+        /// Only returns data attached by [V8Value::set_user_data].
+        fn get_user_data(&self) -> V8UserData {
+            if !self.is_valid().unwrap_or_default() { return None; }
+            get_user_data.and_then(|f| unsafe { V8UserData::from_raw(f(self.get_this())) })
+        }
+
         /// See [cef_v8value_t::get_function_name]
         fn get_function_name(&self) -> CefString {
             get_function_name.and_then(|f| unsafe {
@@ -446,87 +606,496 @@ impl V8Value {
     }
 }
 
+/// Copy `message` into the exception out-param of a V8 callback.
+unsafe fn set_exception(exception: *mut cef_string_t, message: &str) {
+    if exception.is_null() {
+        return;
+    }
+    let message = CefString::from(message);
+    let raw = message.as_raw();
+    cef_string_utf16_set(raw.str_, raw.length, exception, 1);
+}
+
+/// Run a V8 callback, turning an error or a panic into a JS exception.
+unsafe fn catch_exception<T>(
+    exception: *mut cef_string_t,
+    callback: impl FnOnce() -> std::result::Result<T, String>,
+) -> Option<T> {
+    match std::panic::catch_unwind(AssertUnwindSafe(callback)) {
+        Ok(Ok(value)) => Some(value),
+        Ok(Err(e)) => {
+            set_exception(exception, &e);
+            None
+        }
+        Err(e) => {
+            set_exception(exception, &format!("rust panic: {e:?}"));
+            None
+        }
+    }
+}
+
+/// See [cef_v8accessor_t] for more documentation.
+///
+/// Serves the properties registered with [V8Value::set_value_byaccessor] on an object created
+/// by [V8Value::object]. An `Err` is thrown as a JS exception.
+pub trait V8Accessor: Sized {
+    /// See [cef_v8accessor_t::get]
+    fn get(&self, name: CefString, object: V8Value) -> std::result::Result<V8Value, String>;
+
+    /// See [cef_v8accessor_t::set]
+    ///
+    /// Throws by default, making every property read-only.
+    fn set(
+        &self,
+        name: CefString,
+        object: V8Value,
+        value: V8Value,
+    ) -> std::result::Result<(), String> {
+        _ = (object, value);
+        Err(format!(
+            "Cannot assign to read only property '{}'",
+            name.to_string()
+        ))
+    }
+
+    #[doc(hidden)]
+    fn into_raw(self) -> *mut cef_v8accessor_t {
+        unsafe extern "C" fn get<T: V8Accessor>(
+            self_: *mut _cef_v8accessor_t,
+            name: *const cef_string_t,
+            object: *mut _cef_v8value_t,
+            retval: *mut *mut _cef_v8value_t,
+            exception: *mut cef_string_t,
+        ) -> ::std::os::raw::c_int {
+            let accessor: &RcImpl<_, T> = RcImpl::get(self_);
+            let name = CefString::from_raw(name).unwrap_or_default();
+            let object = V8Value::from(object);
+            if let Some(value) = catch_exception(exception, || accessor.interface.get(name, object))
+            {
+                *retval = value.into_raw();
+            }
+            1
+        }
+
+        unsafe extern "C" fn set<T: V8Accessor>(
+            self_: *mut _cef_v8accessor_t,
+            name: *const cef_string_t,
+            object: *mut _cef_v8value_t,
+            value: *mut _cef_v8value_t,
+            exception: *mut cef_string_t,
+        ) -> ::std::os::raw::c_int {
+            let accessor: &RcImpl<_, T> = RcImpl::get(self_);
+            let name = CefString::from_raw(name).unwrap_or_default();
+            let (object, value) = (V8Value::from(object), V8Value::from(value));
+            catch_exception(exception, || accessor.interface.set(name, object, value));
+            1
+        }
+
+        let mut object: cef_v8accessor_t = unsafe { std::mem::zeroed() };
+        object.get = Some(get::<Self>);
+        object.set = Some(set::<Self>);
+        RcImpl::new(object, self).cast()
+    }
+}
+
+/// No accessor, see [V8Value::object].
+impl V8Accessor for () {
+    fn get(&self, name: CefString, _object: V8Value) -> std::result::Result<V8Value, String> {
+        Err(format!("no accessor for '{}'", name.to_string()))
+    }
+
+    fn into_raw(self) -> *mut cef_v8accessor_t {
+        null_mut()
+    }
+}
+
 /// See [cef_v8interceptor_t] for more documentation.
-#[wrapper]
-#[derive(Debug, Clone)]
-pub struct V8Interceptor(cef_v8interceptor_t);
+///
+/// Intercepts every named and indexed property access on an object created by
+/// [V8Value::object]. Getters return `Ok(None)` and setters return `Ok(false)` to leave the
+/// property to V8, which is what the default methods do. An `Err` is thrown as a JS exception.
+#[allow(unused_variables)]
+pub trait V8Interceptor: Sized {
+    /// See [cef_v8interceptor_t::get_byname]
+    fn get_byname(
+        &self,
+        name: CefString,
+        object: V8Value,
+    ) -> std::result::Result<Option<V8Value>, String> {
+        Ok(None)
+    }
+
+    /// See [cef_v8interceptor_t::get_byindex]
+    fn get_byindex(
+        &self,
+        index: usize,
+        object: V8Value,
+    ) -> std::result::Result<Option<V8Value>, String> {
+        Ok(None)
+    }
+
+    /// See [cef_v8interceptor_t::set_byname]
+    ///
+    /// Return `Ok(true)` if the assignment was handled.
+    fn set_byname(
+        &self,
+        name: CefString,
+        object: V8Value,
+        value: V8Value,
+    ) -> std::result::Result<bool, String> {
+        Ok(false)
+    }
+
+    /// See [cef_v8interceptor_t::set_byindex]
+    ///
+    /// Return `Ok(true)` if the assignment was handled.
+    fn set_byindex(
+        &self,
+        index: usize,
+        object: V8Value,
+        value: V8Value,
+    ) -> std::result::Result<bool, String> {
+        Ok(false)
+    }
+
+    #[doc(hidden)]
+    fn into_raw(self) -> *mut cef_v8interceptor_t {
+        unsafe extern "C" fn get_byname<T: V8Interceptor>(
+            self_: *mut _cef_v8interceptor_t,
+            name: *const cef_string_t,
+            object: *mut _cef_v8value_t,
+            retval: *mut *mut _cef_v8value_t,
+            exception: *mut cef_string_t,
+        ) -> ::std::os::raw::c_int {
+            let interceptor: &RcImpl<_, T> = RcImpl::get(self_);
+            let name = CefString::from_raw(name).unwrap_or_default();
+            let object = V8Value::from(object);
+            match catch_exception(exception, || interceptor.interface.get_byname(name, object)) {
+                Some(Some(value)) => {
+                    *retval = value.into_raw();
+                    1
+                }
+                Some(None) => 0,
+                None => 1,
+            }
+        }
+
+        unsafe extern "C" fn get_byindex<T: V8Interceptor>(
+            self_: *mut _cef_v8interceptor_t,
+            index: ::std::os::raw::c_int,
+            object: *mut _cef_v8value_t,
+            retval: *mut *mut _cef_v8value_t,
+            exception: *mut cef_string_t,
+        ) -> ::std::os::raw::c_int {
+            let interceptor: &RcImpl<_, T> = RcImpl::get(self_);
+            let object = V8Value::from(object);
+            match catch_exception(exception, || {
+                interceptor.interface.get_byindex(index as _, object)
+            }) {
+                Some(Some(value)) => {
+                    *retval = value.into_raw();
+                    1
+                }
+                Some(None) => 0,
+                None => 1,
+            }
+        }
+
+        unsafe extern "C" fn set_byname<T: V8Interceptor>(
+            self_: *mut _cef_v8interceptor_t,
+            name: *const cef_string_t,
+            object: *mut _cef_v8value_t,
+            value: *mut _cef_v8value_t,
+            exception: *mut cef_string_t,
+        ) -> ::std::os::raw::c_int {
+            let interceptor: &RcImpl<_, T> = RcImpl::get(self_);
+            let name = CefString::from_raw(name).unwrap_or_default();
+            let (object, value) = (V8Value::from(object), V8Value::from(value));
+            catch_exception(exception, || {
+                interceptor.interface.set_byname(name, object, value)
+            })
+            .unwrap_or(true) as _
+        }
+
+        unsafe extern "C" fn set_byindex<T: V8Interceptor>(
+            self_: *mut _cef_v8interceptor_t,
+            index: ::std::os::raw::c_int,
+            object: *mut _cef_v8value_t,
+            value: *mut _cef_v8value_t,
+            exception: *mut cef_string_t,
+        ) -> ::std::os::raw::c_int {
+            let interceptor: &RcImpl<_, T> = RcImpl::get(self_);
+            let (object, value) = (V8Value::from(object), V8Value::from(value));
+            catch_exception(exception, || {
+                interceptor.interface.set_byindex(index as _, object, value)
+            })
+            .unwrap_or(true) as _
+        }
+
+        let mut object: cef_v8interceptor_t = unsafe { std::mem::zeroed() };
+        object.get_byname = Some(get_byname::<Self>);
+        object.get_byindex = Some(get_byindex::<Self>);
+        object.set_byname = Some(set_byname::<Self>);
+        object.set_byindex = Some(set_byindex::<Self>);
+        RcImpl::new(object, self).cast()
+    }
+}
+
+/// No interceptor, see [V8Value::object].
+impl V8Interceptor for () {
+    fn into_raw(self) -> *mut cef_v8interceptor_t {
+        null_mut()
+    }
+}
+
+/// Address of every live user data created by [V8Value::set_user_data].
+///
+/// User data is an opaque [cef_base_ref_counted_t], so only pointers found here are known to
+/// be a [UserData].
+static USER_DATA: Mutex<BTreeSet<usize>> = Mutex::new(BTreeSet::new());
+
+/// Rust side of the user data created by [V8Value::set_user_data].
+struct UserData(Box<dyn Any>);
+
+impl UserData {
+    fn into_raw<T: Any>(data: T) -> *mut cef_base_ref_counted_t {
+        let object: cef_base_ref_counted_t = unsafe { std::mem::zeroed() };
+        let ptr = RcImpl::new(object, UserData(Box::new(data)));
+        USER_DATA.lock().unwrap().insert(ptr as usize);
+        ptr.cast()
+    }
+}
+
+impl Drop for UserData {
+    fn drop(&mut self) {
+        let offset = std::mem::offset_of!(RcImpl<cef_base_ref_counted_t, UserData>, interface);
+        let ptr = (self as *const Self as usize) - offset;
+        if let Ok(mut user_data) = USER_DATA.lock() {
+            user_data.remove(&ptr);
+        }
+    }
+}
+
+/// Rust value attached to a [V8Value] by [V8Value::set_user_data].
+pub struct V8UserData(RefGuard<cef_base_ref_counted_t>);
+
+impl V8UserData {
+    unsafe fn from_raw(ptr: *mut cef_base_ref_counted_t) -> Option<Self> {
+        let guard = RefGuard::from_raw(NonNull::new(ptr)?);
+        let known = USER_DATA.lock().unwrap().contains(&(ptr as usize));
+        known.then_some(Self(guard))
+    }
+
+    /// Get the value if it's a `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        let object: &RcImpl<cef_base_ref_counted_t, UserData> =
+            RcImpl::get(unsafe { self.0.get_this() });
+        object.interface.0.downcast_ref()
+    }
+}
 
 /// See [cef_v8exception_t] for more documentation.
+///
+/// An exception may be empty when CEF gave no details, e.g. from [CefV8Context::eval] on a
+/// context that can't evaluate scripts. All getters of an empty exception return `None`, and it
+/// displays as `unknown exception`.
 #[derive(Debug, Clone)]
 #[wrapper]
-pub struct V8Excepction(cef_v8exception_t);
+pub struct V8Exception(cef_v8exception_t);
 
-impl V8Excepction {
+impl V8Exception {
     wrapper_methods! {
         /// See [cef_v8exception_t::get_message]
         fn get_message(&self) -> CefString {
              get_message.and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this()))})
         }
+
+        /// See [cef_v8exception_t::get_source_line]
+        fn get_source_line(&self) -> CefString {
+            get_source_line.and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_v8exception_t::get_script_resource_name]
+        fn get_script_resource_name(&self) -> CefString {
+            get_script_resource_name
+                .and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_v8exception_t::get_line_number]
+        fn get_line_number(&self) -> i32;
+
+        /// See [cef_v8exception_t::get_start_position]
+        fn get_start_position(&self) -> i32;
+
+        /// See [cef_v8exception_t::get_end_position]
+        fn get_end_position(&self) -> i32;
+
+        /// See [cef_v8exception_t::get_start_column]
+        fn get_start_column(&self) -> i32;
+
+        /// See [cef_v8exception_t::get_end_column]
+        fn get_end_column(&self) -> i32;
+    }
+}
+
+impl std::fmt::Display for V8Exception {
+    /// Formats as `message (script:line:column)`, the location being omitted when unknown.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_none() {
+            return write!(f, "unknown exception");
+        }
+        let message = self.get_message().unwrap_or_default().to_string();
+        write!(f, "{message}")?;
+        let line = self.get_line_number().unwrap_or_default();
+        if line > 0 {
+            let script = self
+                .get_script_resource_name()
+                .unwrap_or_default()
+                .to_string();
+            let column = self.get_start_column().unwrap_or_default();
+            write!(f, " ({script}:{line}:{})", column + 1)?;
+        }
+        Ok(())
     }
 }
 
-/// See [cef_v8array_buffer_release_callback_t] for more documentation.
-#[derive(Debug, Clone)]
-#[wrapper]
-struct V8ArrayBufferReleaseCallback(cef_v8array_buffer_release_callback_t);
+impl std::error::Error for V8Exception {}
+
+#[deprecated(note = "renamed to `V8Exception`")]
+pub type V8Excepction = V8Exception;
 
 /// See [cef_v8stack_trace_t] for more documentation.
 #[derive(Debug, Clone)]
 #[wrapper]
 pub struct V8StackTrace(cef_v8stack_trace_t);
 
+impl V8StackTrace {
+    /// See [cef_v8stack_trace_get_current].
+    ///
+    /// Capture at most `frame_limit` frames of the currently active context.
+    pub fn get_current(frame_limit: i32) -> Self {
+        unsafe { Self::from(cef_v8stack_trace_get_current(frame_limit as _)) }
+    }
+
+    wrapper_methods! {
+        /// See [cef_v8stack_trace_t::is_valid]
+        fn is_valid(&self) -> bool;
+
+        /// See [cef_v8stack_trace_t::get_frame_count]
+        fn get_frame_count(&self) -> usize {
+            get_frame_count.map(|f| unsafe { f(self.get_this()).max(0) as _ })
+        }
+
+        /// See [cef_v8stack_trace_t::get_frame]
+        fn get_frame(&self, index: usize) -> V8StackFrame {
+            get_frame.and_then(|f| unsafe {
+                let v = f(self.get_this(), index as _);
+                if v.is_null() { None } else { Some(V8StackFrame::from(v)) }
+            })
+        }
+    }
+
+    /// All frames, innermost first.
+    pub fn frames(&self) -> Vec<V8StackFrame> {
+        let count = self.get_frame_count().unwrap_or_default();
+        (0..count)
+            .filter_map(|index| self.get_frame(index))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for V8StackTrace {
+    /// Formats one `at function (script:line:column)` line per frame, like `Error.stack`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, frame) in self.frames().iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "    at {frame}")?;
+        }
+        Ok(())
+    }
+}
+
 /// See [cef_v8stack_frame_t] for more documentation.
 #[derive(Debug, Clone)]
 #[wrapper]
 pub struct V8StackFrame(cef_v8stack_frame_t);
 
-/// See [cef_v8accessor_t] for more documentation.
-#[derive(Debug, Clone)]
-#[wrapper]
-pub struct V8Accessor(cef_v8accessor_t);
+impl V8StackFrame {
+    wrapper_methods! {
+        /// See [cef_v8stack_frame_t::is_valid]
+        fn is_valid(&self) -> bool;
 
-#[derive(Debug, Hash)]
-pub struct JsCallbackId {
-    pub fn_name: CefString,
-    pub browser_id: i32,
-    pub frame_id: CefString,
-}
+        /// See [cef_v8stack_frame_t::get_script_name]
+        fn get_script_name(&self) -> CefString {
+            get_script_name.and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_v8stack_frame_t::get_script_name_or_source_url]
+        fn get_script_name_or_source_url(&self) -> CefString {
+            get_script_name_or_source_url
+                .and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_v8stack_frame_t::get_function_name]
+        fn get_function_name(&self) -> CefString {
+            get_function_name.and_then(|f| unsafe { CefString::from_userfree_cef(f(self.get_this())) })
+        }
+
+        /// See [cef_v8stack_frame_t::get_line_number]
+        fn get_line_number(&self) -> i32;
 
-impl PartialEq for JsCallbackId {
-    fn eq(&self, other: &Self) -> bool {
-        self.browser_id == other.browser_id
-            && self.frame_id.to_string() == other.frame_id.to_string()
-            && self.fn_name.to_string() == other.fn_name.to_string()
+        /// See [cef_v8stack_frame_t::get_column]
+        fn get_column(&self) -> i32;
+
+        /// See [cef_v8stack_frame_t::is_eval]
+        fn is_eval(&self) -> bool;
+
+        /// See [cef_v8stack_frame_t::is_constructor]
+        fn is_constructor(&self) -> bool;
     }
 }
 
-impl Eq for JsCallbackId {}
-
-#[derive(Debug, Clone)]
-pub struct JsCallback {
-    pub context: CefV8Context,
-    pub function: V8Value,
+impl std::fmt::Display for V8StackFrame {
+    /// Formats as `function (script:line:column)`, or `script:line:column` for anonymous code.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let script = self
+            .get_script_name_or_source_url()
+            .unwrap_or_default()
+            .to_string();
+        let line = self.get_line_number().unwrap_or_default();
+        let column = self.get_column().unwrap_or_default();
+        let function = self.get_function_name().unwrap_or_default().to_string();
+        let prefix = if self.is_constructor().unwrap_or_default() {
+            "new "
+        } else {
+            ""
+        };
+        if function.is_empty() {
+            write!(f, "{script}:{line}:{column}")
+        } else {
+            write!(f, "{prefix}{function} ({script}:{line}:{column})")
+        }
+    }
 }
 
 /// See [cef_v8handler_t] for more documentation.
 pub struct V8Handler {
-    inner: Box<
-        dyn Fn(
-                &V8Handler,
-                Option<CefString>, // js name
-                V8Value,           //js this
-                &[V8Value],        // js args
-            ) -> anyhow::Result<V8Value>
-            + Send
-            + Sync
-            + 'static,
-    >,
-    callback_map: Arc<Mutex<HashMap<JsCallbackId, JsCallback>>>,
+    inner: Box<V8HandlerFn>,
 }
 
+/// Called with the handler, the JS function name, `this` and the JS arguments.
+type V8HandlerFn = dyn Fn(&V8Handler, Option<CefString>, V8Value, &[V8Value]) -> anyhow::Result<V8Value>
+    + Send
+    + Sync
+    + 'static;
+
 impl V8Handler {
+    /// Create a handler calling `handler`. An `Err` is thrown as a JS exception.
+    ///
+    /// JS functions received as arguments can be kept with [register_js_callback].
     pub fn new(
-        callback_map: Arc<Mutex<HashMap<JsCallbackId, JsCallback>>>,
         handler: impl Fn(&V8Handler, Option<CefString>, V8Value, &[V8Value]) -> anyhow::Result<V8Value>
             + Send
             + Sync
@@ -534,11 +1103,11 @@ impl V8Handler {
     ) -> Self {
         Self {
             inner: Box::new(handler),
-            callback_map,
         }
     }
 
-    fn execute(
+    /// Call the handler as if JS called the function `name`.
+    pub fn execute(
         &self,
         name: Option<CefString>,
         this: V8Value,
@@ -546,60 +1115,66 @@ impl V8Handler {
     ) -> anyhow::Result<V8Value> {
         (self.inner)(self, name, this, args)
     }
+}
 
-    pub fn insert_callback(&self, id: JsCallbackId, callback: JsCallback) {
-        self.callback_map.try_lock().unwrap().insert(id, callback);
+impl Rc for cef_v8handler_t {
+    fn as_base(&self) -> &cef_base_ref_counted_t {
+        &self.base
     }
+}
+
+/// The [V8Handler] of a function, see [V8Value::get_function_handler].
+pub struct V8FunctionHandler(RefGuard<cef_v8handler_t>);
 
-    pub fn remove_callback(&self, id: &JsCallbackId) -> Option<JsCallback> {
-        self.callback_map.try_lock().unwrap().get(id).cloned()
+impl std::ops::Deref for V8FunctionHandler {
+    type Target = V8Handler;
+
+    fn deref(&self) -> &V8Handler {
+        // Only handlers created by `V8Handler::into_raw` are wrapped, see
+        // `V8Value::get_function_handler`.
+        let object: &RcImpl<cef_v8handler_t, V8Handler> = RcImpl::get(unsafe { self.0.get_this() });
+        &object.interface
+    }
+}
+
+/// The `execute` of every handler created by [V8Handler::into_raw].
+unsafe extern "C" fn execute_handler(
+    self_: *mut _cef_v8handler_t,
+    name: *const cef_string_t,
+    this: *mut _cef_v8value_t,
+    argc: usize,
+    arguments: *const *mut _cef_v8value_t,
+    retval: *mut *mut _cef_v8value_t,
+    exception: *mut cef_string_t,
+) -> ::std::os::raw::c_int {
+    let obj: &mut RcImpl<_, V8Handler> = RcImpl::get(self_);
+    let name = CefString::from_raw(name);
+    let args = if arguments.is_null() {
+        vec![]
+    } else {
+        std::slice::from_raw_parts(arguments, argc)
+            .to_vec()
+            .into_iter()
+            .map(V8Value::from)
+            .collect::<Vec<_>>()
+    };
+    let this = V8Value::from(this);
+    let ret = catch_exception(exception, || {
+        obj.interface
+            .execute(name, this, &args)
+            .map_err(|e| format!("{e:#}"))
+    });
+    if let Some(r) = ret {
+        *retval = r.into_raw();
     }
+    // The exception is only thrown if the call is reported as handled.
+    1
 }
 
 impl V8Handler {
     fn into_raw(self) -> *mut cef_v8handler_t {
-        unsafe extern "C" fn execute(
-            self_: *mut _cef_v8handler_t,
-            name: *const cef_string_t,
-            this: *mut _cef_v8value_t,
-            argc: usize,
-            arguments: *const *mut _cef_v8value_t,
-            retval: *mut *mut _cef_v8value_t,
-            exception: *mut cef_string_t,
-        ) -> ::std::os::raw::c_int {
-            let obj: &mut RcImpl<_, V8Handler> = RcImpl::get(self_);
-            let name = CefString::from_raw(name);
-            let args = if arguments.is_null() {
-                vec![]
-            } else {
-                std::slice::from_raw_parts(arguments, argc)
-                    .to_vec()
-                    .into_iter()
-                    .map(V8Value::from)
-                    .collect::<Vec<_>>()
-            };
-            let this = V8Value::from(this);
-            let ret = std::panic::catch_unwind(AssertUnwindSafe(|| {
-                obj.interface.execute(name, this, &args)
-            }));
-            (match ret {
-                Ok(Ok(r)) => {
-                    *retval = r.into_raw();
-                    true
-                }
-                Ok(Err(e)) => {
-                    *exception = CefString::from(&format!("{e:?}")).as_raw();
-                    false
-                }
-                Err(e) => {
-                    *exception = CefString::from(&format!("rust panic: {e:?}")).as_raw();
-                    false
-                }
-            }) as _
-        }
-
         let mut object: cef_v8handler_t = unsafe { std::mem::zeroed() };
-        object.execute = Some(execute);
+        object.execute = Some(execute_handler);
 
         crate::rc::RcImpl::new(object, self).cast()
     }
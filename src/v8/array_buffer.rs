@@ -0,0 +1,175 @@
+//! ArrayBuffers backed by Rust memory
+//!
+//! [V8Value::array_buffer_from_vec] hands a `Vec` over to V8 without copying it, the `Vec`
+//! being dropped once V8 collects the buffer. The contents of any ArrayBuffer are copied out with
+//! [V8Value::array_buffer_to_vec] and in with [V8Value::copy_into_array_buffer], while the unsafe
+//! [V8Value::with_array_buffer] gives scoped access without copying. Typed array views are
+//! created through their JS constructors, see [V8Value::typed_array].
+
+use std::{any::Any, cell::RefCell, collections::HashSet};
+
+use super::{CefV8Context, V8Value};
+use crate::{prelude::*, rc::RcImpl};
+
+thread_local! {
+    /// Data of the ArrayBuffers currently accessed through [V8Value::with_array_buffer].
+    static BORROWED: RefCell<HashSet<usize>> = RefCell::new(HashSet::new());
+}
+
+/// Owns the memory of an ArrayBuffer until V8 releases it.
+struct ReleaseCallback(Option<Box<dyn Any>>);
+
+impl ReleaseCallback {
+    fn into_raw(self) -> *mut cef_v8array_buffer_release_callback_t {
+        unsafe extern "C" fn release_buffer(
+            self_: *mut _cef_v8array_buffer_release_callback_t,
+            _buffer: *mut core::ffi::c_void,
+        ) {
+            let callback: &mut RcImpl<_, ReleaseCallback> = RcImpl::get(self_);
+            callback.interface.0.take();
+        }
+
+        let mut object: cef_v8array_buffer_release_callback_t = unsafe { std::mem::zeroed() };
+        object.release_buffer = Some(release_buffer);
+        RcImpl::new(object, self).cast()
+    }
+}
+
+/// Removes a buffer from [BORROWED] when the access ends, even by unwinding.
+struct BorrowGuard(usize);
+
+impl Drop for BorrowGuard {
+    fn drop(&mut self) {
+        BORROWED.with_borrow_mut(|borrowed| borrowed.remove(&self.0));
+    }
+}
+
+/// Create an ArrayBuffer over the elements of `values`, without copying them.
+fn array_buffer_from_elements<T: Copy + 'static>(mut values: Vec<T>) -> V8Value {
+    let data = values.as_mut_ptr();
+    let byte_length = std::mem::size_of_val(values.as_slice());
+    let callback = ReleaseCallback(Some(Box::new(values)));
+    unsafe {
+        V8Value::from(cef_v8value_create_array_buffer(
+            data.cast(),
+            byte_length,
+            callback.into_raw(),
+        ))
+    }
+}
+
+impl V8Value {
+    /// See [cef_v8value_create_array_buffer].
+    ///
+    /// Create an ArrayBuffer over the contents of `buffer` without copying them. `buffer` is
+    /// dropped when V8 releases the ArrayBuffer. Must be called with a context entered.
+    pub fn array_buffer_from_vec(buffer: Vec<u8>) -> Self {
+        array_buffer_from_elements(buffer)
+    }
+
+    /// Copy the contents of this ArrayBuffer.
+    ///
+    /// Fails like [V8Value::with_array_buffer].
+    pub fn array_buffer_to_vec(&self) -> Result<Vec<u8>> {
+        // Copying doesn't run JS.
+        unsafe { self.with_array_buffer(|contents| contents.to_vec()) }
+    }
+
+    /// Copy `bytes` to the start of this ArrayBuffer, leaving the rest of its contents as is.
+    ///
+    /// Fails like [V8Value::with_array_buffer], or if `bytes` doesn't fit.
+    pub fn copy_into_array_buffer(&self, bytes: &[u8]) -> Result<()> {
+        // Copying doesn't run JS.
+        unsafe {
+            self.with_array_buffer(|contents| match contents.get_mut(..bytes.len()) {
+                Some(start) => {
+                    start.copy_from_slice(bytes);
+                    Ok(())
+                }
+                None => Err(Error::V8ArrayBufferTooSmall(contents.len())),
+            })?
+        }
+    }
+
+    /// Call `f` with the contents of this ArrayBuffer, which it may read and write, without
+    /// copying them. See [V8Value::array_buffer_to_vec] and [V8Value::copy_into_array_buffer]
+    /// for safe copies.
+    ///
+    /// Fails if no context is entered, if this isn't an ArrayBuffer, or if its contents are
+    /// already accessed further up the stack. The contents are empty once the ArrayBuffer is
+    /// neutered.
+    ///
+    /// # Safety
+    ///
+    /// `f` must not run any JS, e.g. through [V8Value::execute_function] or
+    /// [CefV8Context::eval], nor otherwise use V8. JS could transfer or neuter the ArrayBuffer,
+    /// releasing its contents while `f` still uses them.
+    pub unsafe fn with_array_buffer<R>(&self, f: impl FnOnce(&mut [u8]) -> R) -> Result<R> {
+        if !CefV8Context::in_context() {
+            return Err(Error::CannotEnterV8Context);
+        }
+        if !self.is_array_buffer().unwrap_or_default() {
+            return Err(Error::NotV8ArrayBuffer);
+        }
+        let length = self.get_array_buffer_byte_length().unwrap_or_default();
+        let data = self.get_array_buffer_data().unwrap_or(std::ptr::null_mut());
+        if data.is_null() || length == 0 {
+            return Ok(f(&mut []));
+        }
+        if !BORROWED.with_borrow_mut(|borrowed| borrowed.insert(data as usize)) {
+            return Err(Error::V8ArrayBufferInUse);
+        }
+        let _guard = BorrowGuard(data as usize);
+        Ok(f(unsafe { std::slice::from_raw_parts_mut(data, length) }))
+    }
+
+    /// Create a typed array viewing `buffer` by calling `new constructor(buffer)`, e.g. with
+    /// `"Uint8Array"` or `"DataView"`.
+    ///
+    /// `constructor` is looked up on the global object of the current context. An exception
+    /// thrown by the constructor is returned as [Error::V8Exception].
+    pub fn typed_array(constructor: &str, buffer: &V8Value) -> Result<V8Value> {
+        if !CefV8Context::in_context() {
+            return Err(Error::CannotEnterV8Context);
+        }
+        let global = CefV8Context::get_current()
+            .get_global()
+            .ok_or(Error::NullPtr)?;
+        let function = global.get_value_bykey(constructor).ok_or(Error::NullPtr)?;
+        if !function.is_function().unwrap_or_default() {
+            return Err(Error::NotV8Object(constructor.to_string()));
+        }
+        // V8 functions are called, not constructed, from the C API.
+        let construct = global
+            .get_value_bykey("Reflect")
+            .and_then(|reflect| reflect.get_value_bykey("construct"))
+            .ok_or(Error::NullPtr)?;
+        let args = V8Value::array(1);
+        args.set_value_byindex(0, buffer.clone());
+        let view = construct.execute_function(None, vec![function, args]);
+        match view {
+            Some(view) if view.is_valid().unwrap_or_default() => Ok(view),
+            _ => match construct.get_exception() {
+                Some(exception) => {
+                    construct.clear_exception();
+                    Err(Error::V8Exception(exception))
+                }
+                None => Err(Error::NullPtr),
+            },
+        }
+    }
+
+    /// Create a `Uint8Array` over the contents of `bytes`, without copying them.
+    ///
+    /// See [V8Value::array_buffer_from_vec] and [V8Value::typed_array].
+    pub fn uint8_array(bytes: Vec<u8>) -> Result<V8Value> {
+        Self::typed_array("Uint8Array", &array_buffer_from_elements(bytes))
+    }
+
+    /// Create a `Float32Array` over the contents of `values`, without copying them.
+    ///
+    /// See [V8Value::array_buffer_from_vec] and [V8Value::typed_array].
+    pub fn float32_array(values: Vec<f32>) -> Result<V8Value> {
+        Self::typed_array("Float32Array", &array_buffer_from_elements(values))
+    }
+}
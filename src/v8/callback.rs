@@ -0,0 +1,94 @@
+//! JS functions kept to be called back later
+//!
+//! [register_js_callback] stores a JS function under a generated [JsCallbackId], which can be
+//! sent to other threads to call the function through the renderer task runner. Callbacks are
+//! dropped when their context is released, so pages can't leak them across navigations.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use super::{CefV8Context, V8Value};
+use crate::{
+    prelude::*,
+    task::{currently_on, post_task},
+    CefThreadId,
+};
+
+/// A JS function registered with [register_js_callback], with the context it belongs to.
+#[derive(Debug, Clone)]
+pub struct JsCallback {
+    pub context: CefV8Context,
+    pub function: V8Value,
+}
+
+/// Identifies a [JsCallback] in the registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JsCallbackId(u64);
+
+thread_local! {
+    /// Registered callbacks, on the renderer thread.
+    static CALLBACKS: RefCell<HashMap<JsCallbackId, JsCallback>> = RefCell::new(HashMap::new());
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Register `function` of the current context and return its id.
+///
+/// Must be called on the renderer thread with a context entered, e.g. from a
+/// [V8Handler](crate::V8Handler) receiving the function as an argument.
+pub fn register_js_callback(function: V8Value) -> Result<JsCallbackId> {
+    if !currently_on(CefThreadId::TID_RENDERER) || !CefV8Context::in_context() {
+        return Err(Error::CannotEnterV8Context);
+    }
+    if !function.is_function().unwrap_or_default() {
+        return Err(Error::NotV8Function);
+    }
+    let id = JsCallbackId(NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let callback = JsCallback {
+        context: CefV8Context::get_current(),
+        function,
+    };
+    CALLBACKS.with_borrow_mut(|callbacks| callbacks.insert(id, callback));
+    Ok(id)
+}
+
+impl JsCallbackId {
+    /// Get the callback, if it's still registered. Must be called on the renderer thread.
+    pub fn get(self) -> Option<JsCallback> {
+        CALLBACKS.with_borrow(|callbacks| callbacks.get(&self).cloned())
+    }
+
+    /// Call the function from any thread, with the arguments returned by `args`.
+    ///
+    /// `args` runs on the renderer thread with the context of the callback entered. The call is
+    /// skipped if the callback was removed or its context released in the meantime, and
+    /// exceptions thrown by the function are ignored.
+    pub fn call(self, args: impl FnOnce() -> Vec<V8Value> + Send + 'static) -> Result<()> {
+        post_task(CefThreadId::TID_RENDERER, move || {
+            let Some(callback) = self.get() else {
+                return;
+            };
+            let Ok(_scope) = callback.context.scope() else {
+                return;
+            };
+            callback.function.execute_function(None, args());
+        })
+    }
+
+    /// Unregister the callback, from any thread.
+    pub fn remove(self) -> Result<()> {
+        post_task(CefThreadId::TID_RENDERER, move || {
+            CALLBACKS.with_borrow_mut(|callbacks| callbacks.remove(&self));
+        })
+    }
+}
+
+/// Drop the callbacks of `context`.
+pub(crate) fn context_released(context: &CefV8Context) {
+    CALLBACKS.with_borrow_mut(|callbacks| {
+        callbacks.retain(|_, callback| !callback.context.is_same(context.clone()).unwrap_or(true))
+    });
+}
@@ -0,0 +1,55 @@
+//! V8 extensions
+//!
+//! An extension is JavaScript code run in every frame before the page's own scripts. Functions
+//! declared with the `native function` keyword inside it call into a [V8Handler], which
+//! receives the declared name.
+//!
+//! ```no_run
+//! use cef::{register_v8_extension, CefRenderProcessHandler, V8Handler, V8Value};
+//!
+//! struct Renderer;
+//!
+//! impl CefRenderProcessHandler for Renderer {
+//!     fn on_web_kit_initialized(&self) {
+//!         let code = r#"
+//!             var app = app || {};
+//!             (function() {
+//!                 app.version = function() {
+//!                     native function Version();
+//!                     return Version();
+//!                 };
+//!             })();
+//!         "#;
+//!         let handler = V8Handler::new(|_, name, _, _| {
+//!             match name.map(|name| name.to_string()).as_deref() {
+//!                 Some("Version") => Ok(V8Value::string(env!("CARGO_PKG_VERSION"))),
+//!                 _ => anyhow::bail!("unknown native function"),
+//!             }
+//!         });
+//!         register_v8_extension("v8/app", code, handler).unwrap();
+//!     }
+//! }
+//! ```
+
+use super::V8Handler;
+use crate::{prelude::*, task::currently_on, CefThreadId};
+
+/// See [cef_register_extension].
+///
+/// Register the extension `name`, running `js_code` in every frame and dispatching its
+/// `native function`s to `handler`. Must be called on the renderer thread, typically from
+/// [CefRenderProcessHandler::on_web_kit_initialized](crate::CefRenderProcessHandler::on_web_kit_initialized).
+pub fn register_v8_extension(name: &str, js_code: &str, handler: V8Handler) -> Result<()> {
+    if !currently_on(CefThreadId::TID_RENDERER) {
+        return Err(Error::WrongThread(CefThreadId::TID_RENDERER as _));
+    }
+    let name = CefString::from(name);
+    let js_code = CefString::from(js_code);
+    let registered =
+        unsafe { cef_register_extension(&name.as_raw(), &js_code.as_raw(), handler.into_raw()) };
+    if registered == 1 {
+        Ok(())
+    } else {
+        Err(Error::CannotRegisterV8Extension(name.to_string()))
+    }
+}
@@ -0,0 +1,329 @@
+//! Rust types exposed as JavaScript classes
+//!
+//! A [JsClass] is constructed from JS with `new`, and its instances are objects holding the
+//! Rust value as user data, see [V8Value::set_user_data]. Methods and properties dispatch back
+//! to that value, which is dropped when V8 collects the object.
+//!
+//! The methods are created once per context, on the `prototype` of the constructor, so
+//! `instanceof` works. Properties are accessors of every instance.
+//!
+//! ```no_run
+//! use cef::{register_js_class, FromV8Value, IntoV8Value, JsClass, JsMethod, JsProperty, V8Value};
+//!
+//! struct Counter {
+//!     count: i32,
+//! }
+//!
+//! impl JsClass for Counter {
+//!     const NAME: &'static str = "Counter";
+//!
+//!     fn constructor(args: &[V8Value]) -> Result<Self, String> {
+//!         let count = match args.first() {
+//!             Some(start) => i32::from_v8_value(start)?,
+//!             None => 0,
+//!         };
+//!         Ok(Counter { count })
+//!     }
+//!
+//!     fn methods() -> Vec<JsMethod<Self>> {
+//!         vec![JsMethod {
+//!             name: "increment",
+//!             call: |counter, _args| {
+//!                 counter.count += 1;
+//!                 Ok(counter.count.into_v8_value())
+//!             },
+//!         }]
+//!     }
+//!
+//!     fn properties() -> Vec<JsProperty<Self>> {
+//!         vec![JsProperty {
+//!             name: "count",
+//!             get: |counter| Ok(counter.count.into_v8_value()),
+//!             set: Some(|counter, value| {
+//!                 counter.count = i32::from_v8_value(value)?;
+//!                 Ok(())
+//!             }),
+//!         }]
+//!     }
+//! }
+//!
+//! fn on_context_created(context: &cef::CefV8Context) -> cef::Result<()> {
+//!     // `new app.Counter(1).increment()`
+//!     register_js_class::<Counter>(context, "app")
+//! }
+//! ```
+
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    rc::Rc,
+    sync::Arc,
+};
+
+use super::{
+    js_function::{in_context, object_at_path},
+    CefV8Context, V8Accessor, V8Handler, V8Value,
+};
+use crate::{prelude::*, CefV8PropertyAttribute};
+
+/// A Rust type exposed to JS as a class, see [register_js_class].
+pub trait JsClass: Any + Sized {
+    /// Name of the constructor in JS.
+    const NAME: &'static str;
+
+    /// Create a value for `new NAME(...args)`. An `Err` is thrown as a JS exception.
+    fn constructor(args: &[V8Value]) -> std::result::Result<Self, String>;
+
+    /// Methods of every instance.
+    fn methods() -> Vec<JsMethod<Self>> {
+        Vec::new()
+    }
+
+    /// Properties of every instance.
+    fn properties() -> Vec<JsProperty<Self>> {
+        Vec::new()
+    }
+}
+
+/// A method of a [JsClass].
+pub struct JsMethod<T> {
+    /// Name of the method in JS.
+    pub name: &'static str,
+    /// Called with the instance and the JS arguments. An `Err` is thrown as a JS exception.
+    pub call: fn(&mut T, &[V8Value]) -> std::result::Result<V8Value, String>,
+}
+
+impl<T> Clone for JsMethod<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for JsMethod<T> {}
+
+/// A property of a [JsClass].
+pub struct JsProperty<T> {
+    /// Name of the property in JS.
+    pub name: &'static str,
+    /// Read the property. An `Err` is thrown as a JS exception.
+    pub get: fn(&T) -> std::result::Result<V8Value, String>,
+    /// Assign the property, or `None` for a read-only property.
+    #[allow(clippy::type_complexity)]
+    pub set: Option<fn(&mut T, &V8Value) -> std::result::Result<(), String>>,
+}
+
+impl<T> Clone for JsProperty<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for JsProperty<T> {}
+
+/// A [JsClass] in a context.
+#[derive(Clone)]
+struct Class {
+    context: CefV8Context,
+    type_id: TypeId,
+    /// Holds the methods.
+    prototype: V8Value,
+    /// `Arc<[JsProperty<T>]>`
+    properties: Rc<dyn Any>,
+    /// `Object.getPrototypeOf` and `Object.setPrototypeOf`, as they were when the class was
+    /// created, in case JS replaces them later.
+    get_prototype_of: V8Value,
+    set_prototype_of: V8Value,
+}
+
+thread_local! {
+    /// Classes of the contexts of the renderer thread.
+    static CLASSES: RefCell<Vec<Class>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The [Class] of `T` in the current context, created on first use.
+fn class<T: JsClass>() -> Result<Class> {
+    let context = CefV8Context::get_current();
+    let type_id = TypeId::of::<T>();
+    let class = CLASSES.with_borrow(|classes| {
+        classes
+            .iter()
+            .find(|class| {
+                class.type_id == type_id && class.context.is_same(context.clone()).unwrap_or(false)
+            })
+            .cloned()
+    });
+    if let Some(class) = class {
+        return Ok(class);
+    }
+
+    let object = context
+        .get_global()
+        .ok_or(Error::CannotEnterV8Context)?
+        .get_value_bykey("Object");
+    let function = |name: &str| {
+        object
+            .as_ref()
+            .and_then(|object| object.get_value_bykey(name))
+            .filter(|value| value.is_function().unwrap_or_default())
+            .ok_or(Error::NotV8Function)
+    };
+    let prototype = V8Value::object((), ());
+    for method in T::methods() {
+        let handler = V8Handler::new(move |_, _, this, args| {
+            this.with_instance(|value: &mut T| (method.call)(value, args))
+                .map_err(anyhow::Error::msg)?
+                .map_err(anyhow::Error::msg)
+        });
+        prototype.set_value_bykey(
+            method.name,
+            V8Value::function(method.name, handler),
+            CefV8PropertyAttribute::V8_PROPERTY_ATTRIBUTE_DONTENUM,
+        );
+    }
+    let properties: Arc<[JsProperty<T>]> = T::properties().into();
+    let class = Class {
+        context,
+        type_id,
+        prototype,
+        properties: Rc::new(properties),
+        get_prototype_of: function("getPrototypeOf")?,
+        set_prototype_of: function("setPrototypeOf")?,
+    };
+    CLASSES.with_borrow_mut(|classes| classes.push(class.clone()));
+    Ok(class)
+}
+
+/// Drop the classes of `context`.
+pub(crate) fn context_released(context: &CefV8Context) {
+    CLASSES.with_borrow_mut(|classes| {
+        classes.retain(|class| !class.context.is_same(context.clone()).unwrap_or(true))
+    });
+}
+
+/// Install the constructor of `T` on the object at `object_path` of the global object of
+/// `context`. See [register_js_functions](crate::register_js_functions) for `object_path`.
+///
+/// Call it before the scripts of the page run, e.g. from
+/// [CefRenderProcessHandler::on_context_created](crate::CefRenderProcessHandler::on_context_created).
+pub fn register_js_class<T: JsClass>(context: &CefV8Context, object_path: &str) -> Result<()> {
+    in_context(context, || {
+        let object = object_at_path(context, object_path)?;
+        let class = class::<T>()?;
+        let prototype = class.prototype.clone();
+        let get_prototype_of = class.get_prototype_of.clone();
+        let handler = V8Handler::new(move |_, _, this, args| {
+            // `new` creates `this` from the prototype of the constructor.
+            let construct = get_prototype_of
+                .execute_function(None, vec![this])
+                .is_some_and(|proto| proto.is_same(prototype.clone()).unwrap_or_default());
+            if !construct {
+                anyhow::bail!(
+                    "Class constructor {} cannot be invoked without 'new'",
+                    T::NAME
+                );
+            }
+            let value = T::constructor(args).map_err(anyhow::Error::msg)?;
+            Ok(V8Value::instance(value)?)
+        });
+        let constructor = V8Value::function(T::NAME, handler);
+        constructor.set_value_bykey(
+            "prototype",
+            class.prototype.clone(),
+            CefV8PropertyAttribute::V8_PROPERTY_ATTRIBUTE_NONE,
+        );
+        class.prototype.set_value_bykey(
+            "constructor",
+            constructor.clone(),
+            CefV8PropertyAttribute::V8_PROPERTY_ATTRIBUTE_DONTENUM,
+        );
+        object.set_value_bykey(
+            T::NAME,
+            constructor,
+            CefV8PropertyAttribute::V8_PROPERTY_ATTRIBUTE_NONE,
+        );
+        Ok(())
+    })
+}
+
+impl V8Value {
+    /// Create an instance of the [JsClass] `T` holding `value`, whose prototype holds the methods
+    /// of `T`.
+    ///
+    /// This is what `new T.NAME(...)` returns. Must be called with a context entered.
+    pub fn instance<T: JsClass>(value: T) -> Result<Self> {
+        let class = class::<T>()?;
+        let properties = class
+            .properties
+            .downcast_ref::<Arc<[JsProperty<T>]>>()
+            .cloned()
+            .ok_or(Error::NullPtr)?;
+        let object = V8Value::object(ClassAccessor::<T>(properties.clone()), ());
+        object.set_user_data(RefCell::new(value));
+        for property in properties.iter() {
+            let attribute = if property.set.is_some() {
+                CefV8PropertyAttribute::V8_PROPERTY_ATTRIBUTE_NONE
+            } else {
+                CefV8PropertyAttribute::V8_PROPERTY_ATTRIBUTE_READONLY
+            };
+            object.set_value_byaccessor(property.name, attribute);
+        }
+        class
+            .set_prototype_of
+            .execute_function(None, vec![object.clone(), class.prototype])
+            .ok_or(Error::NullPtr)?;
+        Ok(object)
+    }
+
+    /// Call `f` with the Rust value of this instance of the [JsClass] `T`.
+    ///
+    /// Fails if this isn't an instance of `T`, or if the value is already borrowed by a method
+    /// further up the stack.
+    pub fn with_instance<T: JsClass, R>(
+        &self,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> std::result::Result<R, String> {
+        let user_data = self.get_user_data();
+        let value = user_data
+            .as_ref()
+            .and_then(|data| data.downcast_ref::<RefCell<T>>())
+            .ok_or_else(|| format!("not an instance of {}", T::NAME))?;
+        let mut value = value
+            .try_borrow_mut()
+            .map_err(|_| format!("{} instance is already in use", T::NAME))?;
+        Ok(f(&mut value))
+    }
+}
+
+/// Accessor dispatching the [JsProperty]s of `T` to the instance.
+struct ClassAccessor<T>(Arc<[JsProperty<T>]>);
+
+impl<T> ClassAccessor<T> {
+    fn property(&self, name: &CefString) -> std::result::Result<JsProperty<T>, String> {
+        let name = name.to_string();
+        self.0
+            .iter()
+            .find(|property| property.name == name)
+            .copied()
+            .ok_or_else(|| format!("unknown property '{name}'"))
+    }
+}
+
+impl<T: JsClass> V8Accessor for ClassAccessor<T> {
+    fn get(&self, name: CefString, object: V8Value) -> std::result::Result<V8Value, String> {
+        let property = self.property(&name)?;
+        object.with_instance(|value: &mut T| (property.get)(value))?
+    }
+
+    fn set(
+        &self,
+        name: CefString,
+        object: V8Value,
+        value: V8Value,
+    ) -> std::result::Result<(), String> {
+        let property = self.property(&name)?;
+        let set = property
+            .set
+            .ok_or_else(|| format!("Cannot assign to read only property '{}'", property.name))?;
+        object.with_instance(|instance: &mut T| set(instance, &value))?
+    }
+}
@@ -0,0 +1,398 @@
+//! Typed Rust functions callable from JavaScript
+//!
+//! [`#[js_function]`](crate::js_function) turns a free function into a [JsFunction], which
+//! [register_js_functions] installs on an object of a [CefV8Context]:
+//!
+//! ```no_run
+//! use cef::{js_function, register_js_functions, CefV8Context};
+//!
+//! #[js_function]
+//! fn add(a: i32, b: i32) -> i32 {
+//!     a + b
+//! }
+//!
+//! #[js_function(name = "readFile")]
+//! fn read_file(path: String) -> Result<String, std::io::Error> {
+//!     std::fs::read_to_string(path)
+//! }
+//!
+//! fn on_context_created(context: &CefV8Context) -> cef::Result<()> {
+//!     // `window.app.native.add(1, 2)` and `window.app.native.readFile("...")`
+//!     register_js_functions(
+//!         context,
+//!         "app.native",
+//!         [add::js_function(), read_file::js_function()],
+//!     )
+//! }
+//! ```
+//!
+//! Arguments are converted with [FromV8Value] and the return value with [JsReturn]. A wrong
+//! argument count, a mismatched argument type or an `Err` return is thrown as a JS exception.
+
+use super::{CefV8Context, TsParam, V8Handler, V8Value};
+use crate::{prelude::*, CefV8PropertyAttribute};
+
+/// Conversion from a JS argument, see [`#[js_function]`](crate::js_function).
+pub trait FromV8Value: Sized {
+    /// Convert `value`, or describe why it doesn't fit.
+    fn from_v8_value(value: &V8Value) -> std::result::Result<Self, String>;
+
+    /// The value of an omitted argument, if it may be omitted.
+    fn from_missing() -> Option<Self> {
+        None
+    }
+}
+
+/// Conversion into a JS value, see [`#[js_function]`](crate::js_function).
+pub trait IntoV8Value {
+    fn into_v8_value(self) -> V8Value;
+}
+
+/// Return type of a [`#[js_function]`](crate::js_function).
+///
+/// Implemented for every [IntoV8Value] type, and for `Result`s of them whose error is thrown
+/// as a JS exception.
+pub trait JsReturn {
+    fn into_js_return(self) -> std::result::Result<V8Value, String>;
+}
+
+impl<T: IntoV8Value> JsReturn for T {
+    fn into_js_return(self) -> std::result::Result<V8Value, String> {
+        Ok(self.into_v8_value())
+    }
+}
+
+impl<T: IntoV8Value, E: std::fmt::Display> JsReturn for std::result::Result<T, E> {
+    fn into_js_return(self) -> std::result::Result<V8Value, String> {
+        self.map(IntoV8Value::into_v8_value)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// The JS type of `value`, for error messages.
+fn js_type(value: &V8Value) -> &'static str {
+    let is = |check: fn(&V8Value) -> Option<bool>| check(value).unwrap_or_default();
+    if is(V8Value::is_undefined) {
+        "undefined"
+    } else if is(V8Value::is_null) {
+        "null"
+    } else if is(V8Value::is_bool) {
+        "boolean"
+    } else if is(V8Value::is_double) {
+        "number"
+    } else if is(V8Value::is_string) {
+        "string"
+    } else if is(V8Value::is_array) {
+        "array"
+    } else if is(V8Value::is_array_buffer) {
+        "ArrayBuffer"
+    } else if is(V8Value::is_function) {
+        "function"
+    } else if is(V8Value::is_object) {
+        "object"
+    } else {
+        "invalid value"
+    }
+}
+
+fn mismatch(expected: &str, value: &V8Value) -> String {
+    format!("expected {expected}, got {}", js_type(value))
+}
+
+impl FromV8Value for V8Value {
+    fn from_v8_value(value: &V8Value) -> std::result::Result<Self, String> {
+        Ok(value.clone())
+    }
+}
+
+impl FromV8Value for bool {
+    fn from_v8_value(value: &V8Value) -> std::result::Result<Self, String> {
+        value
+            .is_bool()
+            .unwrap_or_default()
+            .then(|| value.get_bool_value())
+            .flatten()
+            .ok_or_else(|| mismatch("boolean", value))
+    }
+}
+
+impl FromV8Value for f64 {
+    fn from_v8_value(value: &V8Value) -> std::result::Result<Self, String> {
+        value
+            .is_double()
+            .unwrap_or_default()
+            .then(|| value.get_double_value())
+            .flatten()
+            .ok_or_else(|| mismatch("number", value))
+    }
+}
+
+impl FromV8Value for f32 {
+    fn from_v8_value(value: &V8Value) -> std::result::Result<Self, String> {
+        f64::from_v8_value(value).map(|v| v as f32)
+    }
+}
+
+macro_rules! integer_from_v8_value {
+    ($($ty:ty),*) => {$(
+        impl FromV8Value for $ty {
+            fn from_v8_value(value: &V8Value) -> std::result::Result<Self, String> {
+                let v = f64::from_v8_value(value)?;
+                if v.fract() == 0.0 && v >= <$ty>::MIN as f64 && v <= <$ty>::MAX as f64 {
+                    Ok(v as $ty)
+                } else {
+                    Err(format!("expected {}, got {v}", stringify!($ty)))
+                }
+            }
+        }
+    )*};
+}
+
+integer_from_v8_value!(i8, i16, i32, i64, u8, u16, u32, u64, isize, usize);
+
+impl FromV8Value for CefString {
+    fn from_v8_value(value: &V8Value) -> std::result::Result<Self, String> {
+        value
+            .is_string()
+            .unwrap_or_default()
+            .then(|| value.get_string_value())
+            .flatten()
+            .ok_or_else(|| mismatch("string", value))
+    }
+}
+
+impl FromV8Value for String {
+    fn from_v8_value(value: &V8Value) -> std::result::Result<Self, String> {
+        CefString::from_v8_value(value).map(|s| s.to_string())
+    }
+}
+
+/// `null` and `undefined` convert to `None`, and the argument may be omitted.
+impl<T: FromV8Value> FromV8Value for Option<T> {
+    fn from_v8_value(value: &V8Value) -> std::result::Result<Self, String> {
+        if value.is_null().unwrap_or_default() || value.is_undefined().unwrap_or_default() {
+            Ok(None)
+        } else {
+            T::from_v8_value(value).map(Some)
+        }
+    }
+
+    fn from_missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl<T: FromV8Value> FromV8Value for Vec<T> {
+    fn from_v8_value(value: &V8Value) -> std::result::Result<Self, String> {
+        if !value.is_array().unwrap_or_default() {
+            return Err(mismatch("array", value));
+        }
+        (0..value.get_array_length().unwrap_or_default())
+            .map(|i| {
+                let element = value
+                    .get_value_byindex(i)
+                    .ok_or_else(|| format!("cannot read element {i}"))?;
+                T::from_v8_value(&element).map_err(|e| format!("element {i}: {e}"))
+            })
+            .collect()
+    }
+}
+
+impl IntoV8Value for V8Value {
+    fn into_v8_value(self) -> V8Value {
+        self
+    }
+}
+
+/// Converts to `undefined`.
+impl IntoV8Value for () {
+    fn into_v8_value(self) -> V8Value {
+        V8Value::undefined()
+    }
+}
+
+impl IntoV8Value for bool {
+    fn into_v8_value(self) -> V8Value {
+        V8Value::bool(self)
+    }
+}
+
+macro_rules! number_into_v8_value {
+    ($ctor:ident: $($ty:ty),*) => {$(
+        impl IntoV8Value for $ty {
+            fn into_v8_value(self) -> V8Value {
+                V8Value::$ctor(self.into())
+            }
+        }
+    )*};
+}
+
+number_into_v8_value!(int: i8, i16, i32);
+number_into_v8_value!(uint: u8, u16, u32);
+number_into_v8_value!(double: f32, f64);
+
+/// Converts to a number, losing precision beyond 2^53.
+impl IntoV8Value for i64 {
+    fn into_v8_value(self) -> V8Value {
+        match i32::try_from(self) {
+            Ok(v) => V8Value::int(v),
+            Err(_) => V8Value::double(self as f64),
+        }
+    }
+}
+
+/// Converts to a number, losing precision beyond 2^53.
+impl IntoV8Value for u64 {
+    fn into_v8_value(self) -> V8Value {
+        match u32::try_from(self) {
+            Ok(v) => V8Value::uint(v),
+            Err(_) => V8Value::double(self as f64),
+        }
+    }
+}
+
+impl IntoV8Value for &str {
+    fn into_v8_value(self) -> V8Value {
+        V8Value::string(self)
+    }
+}
+
+impl IntoV8Value for String {
+    fn into_v8_value(self) -> V8Value {
+        V8Value::string(&self)
+    }
+}
+
+impl IntoV8Value for CefString {
+    fn into_v8_value(self) -> V8Value {
+        V8Value::string(&self.to_string())
+    }
+}
+
+/// `None` converts to `null`.
+impl<T: IntoV8Value> IntoV8Value for Option<T> {
+    fn into_v8_value(self) -> V8Value {
+        self.map_or_else(V8Value::null, IntoV8Value::into_v8_value)
+    }
+}
+
+impl<T: IntoV8Value> IntoV8Value for Vec<T> {
+    fn into_v8_value(self) -> V8Value {
+        let array = V8Value::array(self.len());
+        for (i, element) in self.into_iter().enumerate() {
+            array.set_value_byindex(i, element.into_v8_value());
+        }
+        array
+    }
+}
+
+/// A Rust function callable from JS, generated by [`#[js_function]`](crate::js_function).
+#[derive(Debug, Clone, Copy)]
+pub struct JsFunction {
+    /// Name of the function in JS.
+    pub name: &'static str,
+    /// Number of declared parameters.
+    pub arity: usize,
+    /// Convert the arguments, call the Rust function and convert its return value.
+    pub call: fn(&[V8Value]) -> std::result::Result<V8Value, String>,
+    /// The declared parameters, for [TypeScriptDeclarations](crate::TypeScriptDeclarations).
+    pub params: &'static [TsParam],
+    /// TypeScript type of the return value.
+    pub returns: &'static str,
+}
+
+impl JsFunction {
+    /// Create the [V8Handler] calling this function.
+    pub fn into_handler(self) -> V8Handler {
+        V8Handler::new(move |_, _, _, args| (self.call)(args).map_err(anyhow::Error::msg))
+    }
+
+    /// Create the JS function object calling this function.
+    pub fn into_v8_value(self) -> V8Value {
+        V8Value::function(self.name, self.into_handler())
+    }
+}
+
+/// Fail if a [JsFunction] got more arguments than it declares.
+#[doc(hidden)]
+pub fn check_js_arity(
+    function: &str,
+    arity: usize,
+    args: &[V8Value],
+) -> std::result::Result<(), String> {
+    if args.len() > arity {
+        Err(format!(
+            "{function}: expected at most {arity} arguments, got {}",
+            args.len()
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Convert argument `index` of a [JsFunction].
+#[doc(hidden)]
+pub fn js_argument<T: FromV8Value>(
+    function: &str,
+    args: &[V8Value],
+    index: usize,
+    name: &str,
+) -> std::result::Result<T, String> {
+    match args.get(index) {
+        Some(value) => T::from_v8_value(value),
+        None => T::from_missing().ok_or_else(|| "missing".to_string()),
+    }
+    .map_err(|e| format!("{function}: argument {} `{name}`: {e}", index + 1))
+}
+
+/// Install `functions` on the object at `object_path` of the global object of `context`.
+///
+/// `object_path` is a dot separated path such as `"app.native"`, with missing objects being
+/// created along the way. An empty path installs the functions on the global object itself.
+pub fn register_js_functions(
+    context: &CefV8Context,
+    object_path: &str,
+    functions: impl IntoIterator<Item = JsFunction>,
+) -> Result<()> {
+    in_context(context, || {
+        let object = object_at_path(context, object_path)?;
+        for function in functions {
+            object.set_value_bykey(
+                function.name,
+                function.into_v8_value(),
+                CefV8PropertyAttribute::V8_PROPERTY_ATTRIBUTE_NONE,
+            );
+        }
+        Ok(())
+    })
+}
+
+/// Run `f` with `context` entered.
+pub(crate) fn in_context<T>(context: &CefV8Context, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    let _scope = context.scope()?;
+    f()
+}
+
+/// Get the object at the dot separated `object_path` of the global object of `context`,
+/// creating missing objects along the way.
+pub(crate) fn object_at_path(context: &CefV8Context, object_path: &str) -> Result<V8Value> {
+    let mut object = context.get_global().ok_or(Error::NullPtr)?;
+    for key in object_path.split('.').filter(|key| !key.is_empty()) {
+        object = match object.get_value_bykey(key) {
+            Some(value) if value.is_object().unwrap_or_default() => value,
+            Some(value) if !value.is_undefined().unwrap_or(true) => {
+                return Err(Error::NotV8Object(key.to_string()));
+            }
+            _ => {
+                let value = V8Value::object((), ());
+                object.set_value_bykey(
+                    key,
+                    value.clone(),
+                    CefV8PropertyAttribute::V8_PROPERTY_ATTRIBUTE_NONE,
+                );
+                value
+            }
+        };
+    }
+    Ok(object)
+}
@@ -0,0 +1,163 @@
+//! JS promises settled from Rust
+//!
+//! A [PromiseHandle] settles its promise from any thread: the value is converted and the
+//! promise settled on the renderer thread, inside the context the promise was created in.
+//! [V8Value::promise_from_future] drives a Rust future on the renderer thread and settles the
+//! promise with its output.
+//!
+//! Pending promises of a context are dropped when the context is released.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    future::Future,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use super::{CefV8Context, IntoV8Value, JsReturn, V8Value};
+use crate::{
+    prelude::*,
+    task::{cancel_local, currently_on, post_task, spawn_local, TaskId},
+    CefThreadId,
+};
+
+/// Produces the settled value once the context is entered.
+type Settle = Box<dyn FnOnce() -> std::result::Result<V8Value, String>>;
+
+struct PendingPromise {
+    context: CefV8Context,
+    promise: V8Value,
+    /// The future settling the promise, see [V8Value::promise_from_future].
+    task: Option<TaskId>,
+}
+
+thread_local! {
+    /// Promises waiting to be settled, on the renderer thread.
+    static PROMISES: RefCell<HashMap<u64, PendingPromise>> = RefCell::new(HashMap::new());
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Create a promise in the current context and register it under a new id.
+fn register_promise() -> Result<(V8Value, u64)> {
+    if !currently_on(CefThreadId::TID_RENDERER) || !CefV8Context::in_context() {
+        return Err(Error::CannotEnterV8Context);
+    }
+    let promise = V8Value::promise();
+    if !promise.is_valid().unwrap_or_default() {
+        return Err(Error::NullPtr);
+    }
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let pending = PendingPromise {
+        context: CefV8Context::get_current(),
+        promise: promise.clone(),
+        task: None,
+    };
+    PROMISES.with_borrow_mut(|promises| promises.insert(id, pending));
+    Ok((promise, id))
+}
+
+/// Settle promise `id` with the value produced by `settle`. Must run on the renderer thread.
+fn settle_promise(id: u64, settle: Settle) {
+    let Some(pending) = PROMISES.with_borrow_mut(|promises| promises.remove(&id)) else {
+        return;
+    };
+    let Ok(_scope) = pending.context.scope() else {
+        return;
+    };
+    match settle() {
+        Ok(value) => pending.promise.resolve_promise(value),
+        Err(message) => pending.promise.reject_promise(&message),
+    };
+}
+
+/// Drop the promises and futures of `context`.
+pub(crate) fn context_released(context: &CefV8Context) {
+    let released = PROMISES.with_borrow_mut(|promises| {
+        let released: Vec<u64> = promises
+            .iter()
+            .filter(|(_, pending)| pending.context.is_same(context.clone()).unwrap_or(true))
+            .map(|(id, _)| *id)
+            .collect();
+        released
+            .iter()
+            .filter_map(|id| promises.remove(id))
+            .collect::<Vec<_>>()
+    });
+    for task in released.iter().filter_map(|pending| pending.task) {
+        cancel_local(task);
+    }
+}
+
+/// Settles a JS promise from any thread.
+///
+/// Dropping the handle without settling it rejects the promise.
+pub struct PromiseHandle {
+    id: Option<u64>,
+}
+
+impl PromiseHandle {
+    /// Create a promise in the current context, along with the handle settling it.
+    ///
+    /// Must be called on the renderer thread with a context entered, e.g. from a [V8Handler].
+    ///
+    /// [V8Handler]: crate::V8Handler
+    pub fn new() -> Result<(V8Value, PromiseHandle)> {
+        let (promise, id) = register_promise()?;
+        Ok((promise, PromiseHandle { id: Some(id) }))
+    }
+
+    /// Resolve the promise with `value`.
+    pub fn resolve<T: IntoV8Value + Send + 'static>(self, value: T) -> Result<()> {
+        self.settle(Ok::<_, String>(value))
+    }
+
+    /// Reject the promise with an error carrying `message`.
+    pub fn reject(self, message: impl Into<String>) -> Result<()> {
+        self.settle(Err::<(), _>(message.into()))
+    }
+
+    /// Resolve the promise with an `Ok` value or reject it with an `Err`, see [JsReturn].
+    pub fn settle<T: JsReturn + Send + 'static>(mut self, result: T) -> Result<()> {
+        let Some(id) = self.id.take() else {
+            return Ok(());
+        };
+        post_task(CefThreadId::TID_RENDERER, move || {
+            settle_promise(id, Box::new(move || result.into_js_return()))
+        })
+    }
+}
+
+impl Drop for PromiseHandle {
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            _ = post_task(CefThreadId::TID_RENDERER, move || {
+                settle_promise(id, Box::new(|| Err("promise handle dropped".to_string())))
+            });
+        }
+    }
+}
+
+impl V8Value {
+    /// Create a promise settled with the output of `future`, see [JsReturn].
+    ///
+    /// `future` is polled on the renderer thread, starting in a later task, and dropped if the
+    /// context is released first. Must be called on the renderer thread with a context entered.
+    pub fn promise_from_future<F>(future: F) -> Result<V8Value>
+    where
+        F: Future + 'static,
+        F::Output: JsReturn + 'static,
+    {
+        let (promise, id) = register_promise()?;
+        let task = spawn_local(CefThreadId::TID_RENDERER, async move {
+            let output = future.await;
+            settle_promise(id, Box::new(move || output.into_js_return()));
+        })?;
+        PROMISES.with_borrow_mut(|promises| {
+            if let Some(pending) = promises.get_mut(&id) {
+                pending.task = Some(task);
+            }
+        });
+        Ok(promise)
+    }
+}
@@ -0,0 +1,844 @@
+//! [serde] bridge for [V8Value]
+//!
+//! [to_v8] and [from_v8] convert between Rust values and V8 values, following the JSON data
+//! model with a few additions:
+//!
+//! - `undefined` and `null` both deserialize as `()` or `None`, and `()`/`None` serialize to `null`.
+//! - ArrayBuffers deserialize as bytes, e.g. into `serde_bytes::ByteBuf` or `Vec<u8>`, and bytes
+//!   serialize to ArrayBuffers. Note that `Vec<u8>` serializes as a sequence, so it becomes an
+//!   array of numbers.
+//! - Dates deserialize as milliseconds since the Unix epoch. Use [V8Date] to serialize a `Date`.
+//! - Enums use the externally tagged representation, like `serde_json`.
+//!
+//! Like `serde_json`, both directions stop at a nesting depth of 128 arrays and objects, which
+//! also rejects cyclic JS objects such as `a.self = a`.
+//!
+//! Both functions must be called on the renderer thread with a V8 context entered.
+//!
+//! ```no_run
+//! # fn example(value: cef::V8Value) -> cef::Result<()> {
+//! #[derive(serde::Deserialize, serde::Serialize)]
+//! struct Point {
+//!     x: f64,
+//!     y: f64,
+//! }
+//!
+//! let point: Point = cef::from_v8(&value)?;
+//! let value = cef::to_v8(&Point { x: point.y, y: point.x })?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use serde::{
+    de::{self, IntoDeserializer, Unexpected},
+    ser, Deserialize, Serialize,
+};
+
+use super::V8Value;
+use crate::{prelude::*, CefBaseTime, CefV8PropertyAttribute};
+
+/// Microseconds between the Windows epoch used by [CefBaseTime] and the Unix epoch.
+const UNIX_EPOCH_OFFSET: i64 = 11_644_473_600_000_000;
+
+/// Newtype name marking a [V8Date] to the V8 serializer and deserializer.
+const DATE_TOKEN: &str = "$cef::V8Date";
+
+/// Maximum nesting depth of arrays and objects.
+const RECURSION_LIMIT: usize = 128;
+
+/// Convert `value` into a [V8Value].
+///
+/// Fails if the [Serialize] implementation fails, if a map key isn't a string or a number,
+/// if the value is nested too deeply, or if no V8 context is entered.
+pub fn to_v8<T: Serialize + ?Sized>(value: &T) -> Result<V8Value> {
+    value.serialize(Serializer(RECURSION_LIMIT))
+}
+
+/// Convert a [V8Value] into `T`.
+///
+/// Fails with [Error::V8Serde] describing the mismatch if `value` doesn't have the shape of `T`,
+/// or if it's nested too deeply, e.g. because it's cyclic.
+pub fn from_v8<T: de::DeserializeOwned>(value: &V8Value) -> Result<T> {
+    T::deserialize(Deserializer {
+        value: value.clone(),
+        depth: RECURSION_LIMIT,
+    })
+}
+
+/// A JS `Date`, as milliseconds since the Unix epoch.
+///
+/// [to_v8] turns it into a `Date` object and [from_v8] reads it back from one. Other
+/// serializers see a plain number.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct V8Date(pub f64);
+
+impl From<CefBaseTime> for V8Date {
+    fn from(time: CefBaseTime) -> Self {
+        Self((time.val - UNIX_EPOCH_OFFSET) as f64 / 1000.0)
+    }
+}
+
+impl From<V8Date> for CefBaseTime {
+    fn from(date: V8Date) -> Self {
+        CefBaseTime {
+            val: (date.0 * 1000.0) as i64 + UNIX_EPOCH_OFFSET,
+        }
+    }
+}
+
+impl Serialize for V8Date {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(DATE_TOKEN, &self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for V8Date {
+    fn deserialize<D: de::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = V8Date;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a date or a number of milliseconds")
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> std::result::Result<V8Date, E> {
+                Ok(V8Date(v))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> std::result::Result<V8Date, E> {
+                Ok(V8Date(v as f64))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> std::result::Result<V8Date, E> {
+                Ok(V8Date(v as f64))
+            }
+
+            fn visit_newtype_struct<D: de::Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> std::result::Result<V8Date, D::Error> {
+                f64::deserialize(deserializer).map(V8Date)
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(DATE_TOKEN, Visitor)
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::V8Serde(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::V8Serde(msg.to_string())
+    }
+}
+
+/// Fail if creating `value` returned null, which happens outside a V8 context.
+fn created(value: V8Value) -> Result<V8Value> {
+    if value.is_valid().unwrap_or_default() {
+        Ok(value)
+    } else {
+        Err(Error::V8Serde(
+            "cannot create V8 value, no V8 context is entered".into(),
+        ))
+    }
+}
+
+/// The remaining nesting depth inside an array or object, failing past [RECURSION_LIMIT].
+fn nested(depth: usize) -> Result<usize> {
+    depth
+        .checked_sub(1)
+        .ok_or_else(|| Error::V8Serde("recursion limit exceeded".into()))
+}
+
+fn new_object() -> Result<V8Value> {
+    created(V8Value::object((), ()))
+}
+
+fn set_key(object: &V8Value, key: &str, value: V8Value) {
+    object.set_value_bykey(
+        key,
+        value,
+        CefV8PropertyAttribute::V8_PROPERTY_ATTRIBUTE_NONE,
+    );
+}
+
+/// Serializer producing [V8Value]s, with the remaining nesting depth.
+struct Serializer(usize);
+
+impl ser::Serializer for Serializer {
+    type Ok = V8Value;
+    type Error = Error;
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeTupleVariant;
+    type SerializeMap = SerializeObject;
+    type SerializeStruct = SerializeObject;
+    type SerializeStructVariant = SerializeStructVariant;
+
+    fn serialize_bool(self, v: bool) -> Result<V8Value> {
+        Ok(V8Value::bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<V8Value> {
+        Ok(V8Value::int(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<V8Value> {
+        Ok(V8Value::int(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<V8Value> {
+        Ok(V8Value::int(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<V8Value> {
+        Ok(match (i32::try_from(v), u32::try_from(v)) {
+            (Ok(v), _) => V8Value::int(v),
+            (_, Ok(v)) => V8Value::uint(v),
+            _ => V8Value::double(v as f64),
+        })
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<V8Value> {
+        Ok(V8Value::uint(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<V8Value> {
+        Ok(V8Value::uint(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<V8Value> {
+        Ok(V8Value::uint(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<V8Value> {
+        Ok(match u32::try_from(v) {
+            Ok(v) => V8Value::uint(v),
+            Err(_) => V8Value::double(v as f64),
+        })
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<V8Value> {
+        Ok(V8Value::double(v.into()))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<V8Value> {
+        Ok(V8Value::double(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<V8Value> {
+        Ok(V8Value::string(v.encode_utf8(&mut [0; 4])))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<V8Value> {
+        Ok(V8Value::string(v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<V8Value> {
+        created(V8Value::array_buffer_with_copy(v))
+    }
+
+    fn serialize_none(self) -> Result<V8Value> {
+        Ok(V8Value::null())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<V8Value> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<V8Value> {
+        Ok(V8Value::null())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<V8Value> {
+        Ok(V8Value::null())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<V8Value> {
+        Ok(V8Value::string(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<V8Value> {
+        let value = value.serialize(self)?;
+        if name != DATE_TOKEN {
+            return Ok(value);
+        }
+        let millis = value
+            .get_double_value()
+            .ok_or_else(|| Error::V8Serde("date must be a number of milliseconds".into()))?;
+        created(V8Value::date(V8Date(millis).into()))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<V8Value> {
+        let depth = nested(self.0)?;
+        let object = new_object()?;
+        set_key(&object, variant, value.serialize(Serializer(depth))?);
+        Ok(object)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray> {
+        Ok(SerializeArray {
+            depth: nested(self.0)?,
+            array: created(V8Value::array(len.unwrap_or_default()))?,
+            index: 0,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SerializeArray> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeTupleVariant> {
+        let depth = nested(self.0)?;
+        Ok(SerializeTupleVariant {
+            variant,
+            array: Serializer(depth).serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeObject> {
+        Ok(SerializeObject {
+            depth: nested(self.0)?,
+            object: new_object()?,
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeObject> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeStructVariant> {
+        let depth = nested(self.0)?;
+        Ok(SerializeStructVariant {
+            variant,
+            object: Serializer(depth).serialize_map(Some(len))?,
+        })
+    }
+}
+
+struct SerializeArray {
+    depth: usize,
+    array: V8Value,
+    index: usize,
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = V8Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.array
+            .set_value_byindex(self.index, value.serialize(Serializer(self.depth))?);
+        self.index += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<V8Value> {
+        Ok(self.array)
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = V8Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<V8Value> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = V8Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<V8Value> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct SerializeTupleVariant {
+    variant: &'static str,
+    array: SerializeArray,
+}
+
+impl ser::SerializeTupleVariant for SerializeTupleVariant {
+    type Ok = V8Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeSeq::serialize_element(&mut self.array, value)
+    }
+
+    fn end(self) -> Result<V8Value> {
+        let object = new_object()?;
+        set_key(&object, self.variant, self.array.array);
+        Ok(object)
+    }
+}
+
+struct SerializeObject {
+    depth: usize,
+    object: V8Value,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for SerializeObject {
+    type Ok = V8Value;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        let key = key.serialize(Serializer(self.depth))?;
+        let key = if key.is_string().unwrap_or_default() {
+            key.get_string_value().unwrap_or_default().to_string()
+        } else if key.is_int().unwrap_or_default() {
+            key.get_int_value().unwrap_or_default().to_string()
+        } else if key.is_uint().unwrap_or_default() {
+            key.get_uint_value().unwrap_or_default().to_string()
+        } else if key.is_double().unwrap_or_default() {
+            key.get_double_value().unwrap_or_default().to_string()
+        } else {
+            return Err(Error::V8Serde(
+                "object key must be a string or a number".into(),
+            ));
+        };
+        self.key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::V8Serde("object value serialized before its key".into()))?;
+        set_key(&self.object, &key, value.serialize(Serializer(self.depth))?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<V8Value> {
+        Ok(self.object)
+    }
+}
+
+impl ser::SerializeStruct for SerializeObject {
+    type Ok = V8Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        set_key(&self.object, key, value.serialize(Serializer(self.depth))?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<V8Value> {
+        Ok(self.object)
+    }
+}
+
+struct SerializeStructVariant {
+    variant: &'static str,
+    object: SerializeObject,
+}
+
+impl ser::SerializeStructVariant for SerializeStructVariant {
+    type Ok = V8Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        ser::SerializeStruct::serialize_field(&mut self.object, key, value)
+    }
+
+    fn end(self) -> Result<V8Value> {
+        let object = new_object()?;
+        set_key(&object, self.variant, self.object.object);
+        Ok(object)
+    }
+}
+
+/// The JS type of a [V8Value], as far as deserialization is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Undefined,
+    Null,
+    Bool,
+    Int,
+    UInt,
+    Double,
+    String,
+    Date,
+    ArrayBuffer,
+    Array,
+    Function,
+    Promise,
+    Object,
+}
+
+/// A type check such as [V8Value::is_array].
+type Check = fn(&V8Value) -> Option<bool>;
+
+impl Kind {
+    fn of(value: &V8Value) -> Result<Self> {
+        let is = |f: Check| f(value).unwrap_or_default();
+        if !is(V8Value::is_valid) {
+            return Err(Error::V8Serde("invalid V8 value".into()));
+        }
+        // Dates, ArrayBuffers, arrays, functions and promises are objects as well, so they are
+        // checked first. Every number is a double and only some are ints.
+        let kinds: [(Check, Kind); 12] = [
+            (V8Value::is_undefined, Kind::Undefined),
+            (V8Value::is_null, Kind::Null),
+            (V8Value::is_bool, Kind::Bool),
+            (V8Value::is_int, Kind::Int),
+            (V8Value::is_uint, Kind::UInt),
+            (V8Value::is_double, Kind::Double),
+            (V8Value::is_string, Kind::String),
+            (V8Value::is_date, Kind::Date),
+            (V8Value::is_array_buffer, Kind::ArrayBuffer),
+            (V8Value::is_array, Kind::Array),
+            (V8Value::is_function, Kind::Function),
+            (V8Value::is_promise, Kind::Promise),
+        ];
+        Ok(kinds
+            .into_iter()
+            .find(|(f, _)| is(*f))
+            .map(|(_, kind)| kind)
+            .unwrap_or(Kind::Object))
+    }
+
+    fn unexpected(self) -> Unexpected<'static> {
+        match self {
+            Kind::Undefined => Unexpected::Other("undefined"),
+            Kind::Null => Unexpected::Other("null"),
+            Kind::Bool => Unexpected::Other("boolean"),
+            Kind::Int | Kind::UInt | Kind::Double => Unexpected::Other("number"),
+            Kind::String => Unexpected::Other("string"),
+            Kind::Date => Unexpected::Other("Date"),
+            Kind::ArrayBuffer => Unexpected::Other("ArrayBuffer"),
+            Kind::Array => Unexpected::Seq,
+            Kind::Function => Unexpected::Other("function"),
+            Kind::Promise => Unexpected::Other("Promise"),
+            Kind::Object => Unexpected::Map,
+        }
+    }
+}
+
+/// Copy the contents of an ArrayBuffer.
+fn array_buffer_bytes(value: &V8Value) -> Vec<u8> {
+    let len = value.get_array_buffer_byte_length().unwrap_or_default();
+    match value.get_array_buffer_data() {
+        Some(data) if !data.is_null() && len > 0 => unsafe {
+            std::slice::from_raw_parts(data, len).to_vec()
+        },
+        _ => Vec::new(),
+    }
+}
+
+/// Deserializer reading from a [V8Value].
+struct Deserializer {
+    value: V8Value,
+    /// Remaining nesting depth.
+    depth: usize,
+}
+
+impl Deserializer {
+    fn visit_number<'de, V: de::Visitor<'de>>(&self, visitor: V) -> Result<V::Value> {
+        const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+        let v = self.value.get_double_value().unwrap_or_default();
+        // Let integer targets accept integral numbers that don't fit in 32 bits.
+        if v.fract() == 0.0 && v.abs() <= MAX_SAFE_INTEGER {
+            if v < 0.0 {
+                visitor.visit_i64(v as i64)
+            } else {
+                visitor.visit_u64(v as u64)
+            }
+        } else {
+            visitor.visit_f64(v)
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let value = &self.value;
+        match Kind::of(value)? {
+            Kind::Undefined | Kind::Null => visitor.visit_unit(),
+            Kind::Bool => visitor.visit_bool(value.get_bool_value().unwrap_or_default()),
+            Kind::Int => visitor.visit_i32(value.get_int_value().unwrap_or_default()),
+            Kind::UInt => visitor.visit_u32(value.get_uint_value().unwrap_or_default()),
+            Kind::Double => self.visit_number(visitor),
+            Kind::String => {
+                visitor.visit_string(value.get_string_value().unwrap_or_default().to_string())
+            }
+            Kind::Date => {
+                let time = value.get_date_value().unwrap_or(CefBaseTime { val: 0 });
+                visitor.visit_f64(V8Date::from(time).0)
+            }
+            Kind::ArrayBuffer => visitor.visit_byte_buf(array_buffer_bytes(value)),
+            Kind::Array => visitor.visit_seq(SeqAccess {
+                depth: nested(self.depth)?,
+                len: value.get_array_length().unwrap_or_default(),
+                array: self.value,
+                index: 0,
+            }),
+            Kind::Object => visitor.visit_map(MapAccess {
+                depth: nested(self.depth)?,
+                keys: value.get_keys().unwrap_or_default().into_iter(),
+                object: self.value,
+                value: None,
+            }),
+            kind @ (Kind::Function | Kind::Promise) => {
+                Err(de::Error::invalid_type(kind.unexpected(), &visitor))
+            }
+        }
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match Kind::of(&self.value)? {
+            Kind::Undefined | Kind::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match Kind::of(&self.value)? {
+            // Let `Vec<u8>` and friends read ArrayBuffers.
+            Kind::ArrayBuffer => visitor.visit_seq(de::value::SeqDeserializer::new(
+                array_buffer_bytes(&self.value).into_iter(),
+            )),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        if name == DATE_TOKEN {
+            self.deserialize_any(visitor)
+        } else {
+            visitor.visit_newtype_struct(self)
+        }
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match Kind::of(&self.value)? {
+            Kind::String => visitor.visit_enum(
+                self.value
+                    .get_string_value()
+                    .unwrap_or_default()
+                    .to_string()
+                    .into_deserializer(),
+            ),
+            Kind::Object => {
+                let depth = nested(self.depth)?;
+                let keys = self.value.get_keys().unwrap_or_default();
+                let [variant] = keys.as_slice() else {
+                    return Err(de::Error::invalid_length(
+                        keys.len(),
+                        &"an object with a single key",
+                    ));
+                };
+                let variant = variant.to_string();
+                let value = self
+                    .value
+                    .get_value_bykey(&variant)
+                    .ok_or_else(|| Error::V8Serde(format!("cannot read property `{variant}`")))?;
+                visitor.visit_enum(EnumAccess {
+                    variant,
+                    value,
+                    depth,
+                })
+            }
+            kind => Err(de::Error::invalid_type(
+                kind.unexpected(),
+                &"a string or an object with a single key",
+            )),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct SeqAccess {
+    depth: usize,
+    array: V8Value,
+    len: usize,
+    index: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>> {
+        if self.index >= self.len {
+            return Ok(None);
+        }
+        let index = self.index;
+        self.index += 1;
+        let value = self
+            .array
+            .get_value_byindex(index)
+            .ok_or_else(|| Error::V8Serde(format!("cannot read array element {index}")))?;
+        let depth = self.depth;
+        seed.deserialize(Deserializer { value, depth }).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.index)
+    }
+}
+
+struct MapAccess {
+    depth: usize,
+    object: V8Value,
+    keys: std::vec::IntoIter<CefString>,
+    value: Option<V8Value>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        let Some(key) = self.keys.next() else {
+            return Ok(None);
+        };
+        let key = key.to_string();
+        let value = self
+            .object
+            .get_value_bykey(&key)
+            .ok_or_else(|| Error::V8Serde(format!("cannot read property `{key}`")))?;
+        self.value = Some(value);
+        seed.deserialize(key.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error::V8Serde("object value read before its key".into()))?;
+        let depth = self.depth;
+        seed.deserialize(Deserializer { value, depth })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.keys.len())
+    }
+}
+
+struct EnumAccess {
+    variant: String,
+    value: V8Value,
+    depth: usize,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = Error;
+    type Variant = Deserializer;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Deserializer)> {
+        let variant =
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(self.variant))?;
+        let Self { value, depth, .. } = self;
+        Ok((variant, Deserializer { value, depth }))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Deserializer {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
@@ -0,0 +1,260 @@
+//! TypeScript declarations of the Rust APIs exposed to JS
+//!
+//! [`#[js_function]`](crate::js_function) and `#[command]` record the TypeScript types of their
+//! parameters and return value, mapped from the Rust types: numbers, strings and booleans,
+//! `Option<T>` as `T | null`, sequences as arrays and maps as `Record<string, T>`. Other types,
+//! such as [V8Value] or structs, are `unknown`.
+//!
+//! [TypeScriptDeclarations] collects them into a `.d.ts` file, e.g. from a build script or a
+//! test, so front-end code is checked against the Rust side:
+//!
+//! ```no_run
+//! use cef::{js_function, TypeScriptDeclarations};
+//!
+//! #[js_function]
+//! fn add(a: i32, b: i32) -> i32 {
+//!     a + b
+//! }
+//!
+//! fn write_declarations() -> std::io::Result<()> {
+//!     // `declare namespace app.native { function add(a: number, b: number): number; }`
+//!     TypeScriptDeclarations::new()
+//!         .functions("app.native", [add::js_function()])
+//!         .write("frontend/src/native.d.ts")
+//! }
+//! ```
+
+use std::{collections::BTreeMap, fmt, path::Path};
+
+use super::JsFunction;
+
+/// A parameter of a [JsFunction] or a `Command`, see the [module docs](crate::TypeScriptDeclarations).
+#[derive(Debug, Clone, Copy)]
+pub struct TsParam {
+    pub name: &'static str,
+    /// TypeScript type of the parameter.
+    pub ty: &'static str,
+    /// `true` if the parameter may be omitted.
+    pub optional: bool,
+}
+
+impl TsParam {
+    fn declaration(&self, optional: bool) -> String {
+        let marker = if optional { "?" } else { "" };
+        format!("{}{marker}: {}", self.name, self.ty)
+    }
+}
+
+/// Render `params` as a parameter list, only a trailing run of optional parameters being marked
+/// optional.
+fn parameter_list(params: &[TsParam]) -> String {
+    let required = params.iter().rposition(|param| !param.optional);
+    params
+        .iter()
+        .enumerate()
+        .map(|(index, param)| param.declaration(required.is_none_or(|required| index > required)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A `.d.ts` file describing the Rust APIs exposed to JS, see the [module docs](self).
+#[derive(Debug, Clone, Default)]
+pub struct TypeScriptDeclarations {
+    /// Function declarations by object path.
+    namespaces: BTreeMap<String, Vec<String>>,
+    /// Command declarations by name.
+    commands: BTreeMap<&'static str, String>,
+}
+
+impl TypeScriptDeclarations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare `functions`, installed at `object_path` by
+    /// [register_js_functions](crate::register_js_functions).
+    pub fn functions(
+        mut self,
+        object_path: &str,
+        functions: impl IntoIterator<Item = JsFunction>,
+    ) -> Self {
+        let declarations = self
+            .namespaces
+            .entry(object_path.trim_matches('.').to_string())
+            .or_default();
+        declarations.extend(functions.into_iter().map(|function| {
+            format!(
+                "function {}({}): {};",
+                function.name,
+                parameter_list(function.params),
+                function.returns
+            )
+        }));
+        self
+    }
+
+    /// Declare `cef.invoke` for `commands`, see [Commands](crate::Commands).
+    #[cfg(feature = "serde")]
+    pub fn commands(mut self, commands: impl IntoIterator<Item = crate::Command>) -> Self {
+        for command in commands {
+            let args = if command.params.is_empty() {
+                "{}".to_string()
+            } else {
+                let properties: Vec<String> = command
+                    .params
+                    .iter()
+                    .map(|param| param.declaration(param.optional))
+                    .collect();
+                format!("{{ {} }}", properties.join("; "))
+            };
+            let declaration = format!(
+                "{:?}: {{ args: {args}; returns: {} }};",
+                command.name, command.returns
+            );
+            self.commands.insert(command.name, declaration);
+        }
+        self
+    }
+
+    /// Write the declarations to `path`.
+    pub fn write(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+}
+
+impl fmt::Display for TypeScriptDeclarations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "// Generated by cef::TypeScriptDeclarations, do not edit."
+        )?;
+        for (path, declarations) in &self.namespaces {
+            writeln!(f)?;
+            if path.is_empty() {
+                for declaration in declarations {
+                    writeln!(f, "declare {declaration}")?;
+                }
+            } else {
+                writeln!(f, "declare namespace {path} {{")?;
+                for declaration in declarations {
+                    writeln!(f, "    {declaration}")?;
+                }
+                writeln!(f, "}}")?;
+            }
+        }
+        if !self.commands.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "interface CefCommands {{")?;
+            for declaration in self.commands.values() {
+                writeln!(f, "    {declaration}")?;
+            }
+            writeln!(f, "}}")?;
+            writeln!(f)?;
+            writeln!(f, "declare namespace cef {{")?;
+            writeln!(f, "    function invoke<K extends keyof CefCommands>(")?;
+            writeln!(f, "        command: K,")?;
+            writeln!(
+                f,
+                "        ...args: {{}} extends CefCommands[K][\"args\"]\n            \
+                 ? [args?: CefCommands[K][\"args\"]]\n            \
+                 : [args: CefCommands[K][\"args\"]]"
+            )?;
+            writeln!(f, "    ): Promise<CefCommands[K][\"returns\"]>;")?;
+            writeln!(f, "}}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parameter_list, TsParam, TypeScriptDeclarations};
+
+    fn param(name: &'static str, optional: bool) -> TsParam {
+        TsParam {
+            name,
+            ty: "number",
+            optional,
+        }
+    }
+
+    #[test]
+    fn only_trailing_parameters_are_optional() {
+        assert_eq!(parameter_list(&[]), "");
+        assert_eq!(
+            parameter_list(&[param("a", false), param("b", true), param("c", true)]),
+            "a: number, b?: number, c?: number"
+        );
+        assert_eq!(
+            parameter_list(&[param("a", true), param("b", false), param("c", true)]),
+            "a: number, b: number, c?: number"
+        );
+        assert_eq!(
+            parameter_list(&[param("a", true), param("b", true)]),
+            "a?: number, b?: number"
+        );
+    }
+
+    #[crate::js_function]
+    fn add(a: i32, b: Option<i32>) -> i32 {
+        a + b.unwrap_or_default()
+    }
+
+    #[crate::js_function(name = "listFiles")]
+    fn list_files(_dir: String) -> Result<Vec<String>, String> {
+        Ok(Vec::new())
+    }
+
+    #[test]
+    fn namespaces() {
+        let declarations = TypeScriptDeclarations::new()
+            .functions("app.native", [add::js_function()])
+            .functions("", [list_files::js_function()])
+            .to_string();
+        assert_eq!(
+            declarations,
+            "// Generated by cef::TypeScriptDeclarations, do not edit.\n\
+             \n\
+             declare function listFiles(_dir: string): string[];\n\
+             \n\
+             declare namespace app.native {\n    \
+                 function add(a: number, b?: number | null): number;\n\
+             }\n"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[crate::command(origins = ["*"])]
+    fn greet(name: String, greeting: Option<String>) -> String {
+        format!("{}, {name}!", greeting.as_deref().unwrap_or("Hello"))
+    }
+
+    #[cfg(feature = "serde")]
+    #[crate::command(name = "ping", origins = ["*"])]
+    fn ping() {}
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn commands() {
+        let declarations = TypeScriptDeclarations::new()
+            .commands([greet::command(), ping::command()])
+            .to_string();
+        let expected = r#"// Generated by cef::TypeScriptDeclarations, do not edit.
+
+interface CefCommands {
+    "greet": { args: { name: string; greeting?: string | null }; returns: string };
+    "ping": { args: {}; returns: void };
+}
+
+declare namespace cef {
+    function invoke<K extends keyof CefCommands>(
+        command: K,
+        ...args: {} extends CefCommands[K]["args"]
+            ? [args?: CefCommands[K]["args"]]
+            : [args: CefCommands[K]["args"]]
+    ): Promise<CefCommands[K]["returns"]>;
+}
+"#;
+        assert_eq!(declarations, expected);
+    }
+}
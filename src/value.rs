@@ -141,3 +141,156 @@ impl CefValue {
         }
     );
 }
+
+/// Key of the dictionaries holding the integers that don't fit in an `i32`, as decimal strings,
+/// since a double can't hold every `i64` and `u64`.
+/// Tags of the binary values holding integers that don't fit in an `i32`, followed by the
+/// little-endian bytes of the integer.
+const I64_TAG: &[u8] = b"$cef::i64";
+const U64_TAG: &[u8] = b"$cef::u64";
+
+impl CefValue {
+    /// Create a value holding `json`.
+    ///
+    /// Integers that don't fit in an `i32` are stored losslessly as a binary value holding a
+    /// `$cef::i64` or `$cef::u64` tag and the little-endian bytes of the integer, which
+    /// [to_json](Self::to_json) turns back into the integer.
+    pub fn from_json(json: &serde_json::Value) -> Result<CefValue> {
+        let value = CefValue::create()?;
+        let ok = match json {
+            serde_json::Value::Null => value.set_null(),
+            serde_json::Value::Bool(b) => value.set_bool(*b),
+            serde_json::Value::Number(n) => match n.as_i64().map(i32::try_from) {
+                Some(Ok(i)) => value.set_int(i),
+                _ if n.is_f64() => value.set_double(n.as_f64().unwrap_or(f64::NAN)),
+                _ => {
+                    let bytes = match (n.as_i64(), n.as_u64()) {
+                        (Some(i), _) => [I64_TAG, &i.to_le_bytes()].concat(),
+                        (None, Some(u)) => [U64_TAG, &u.to_le_bytes()].concat(),
+                        (None, None) => return Err(Error::NullPtr),
+                    };
+                    value.set_binary(CefBinaryValue::create(&bytes)?)
+                }
+            },
+            serde_json::Value::String(s) => value.set_string(s),
+            serde_json::Value::Array(items) => {
+                let list = CefListValue::create()?;
+                list.set_size(items.len());
+                for (index, item) in items.iter().enumerate() {
+                    list.set_value(index, CefValue::from_json(item)?);
+                }
+                value.set_list(list)
+            }
+            serde_json::Value::Object(entries) => {
+                let dictionary = CefDictionaryValue::create()?;
+                for (key, entry) in entries {
+                    dictionary.set_value(key, CefValue::from_json(entry)?);
+                }
+                value.set_dictionary(dictionary)
+            }
+        };
+        if ok.unwrap_or_default() {
+            Ok(value)
+        } else {
+            Err(Error::NullPtr)
+        }
+    }
+
+    /// Convert this value to JSON.
+    ///
+    /// Binary values become arrays of bytes, except the integers stored by
+    /// [from_json](Self::from_json), and non-finite doubles become `null`.
+    pub fn to_json(&self) -> Result<serde_json::Value> {
+        use cef_value_type_t::*;
+
+        let json = match self.get_type().unwrap_or(VTYPE_INVALID) {
+            VTYPE_NULL => serde_json::Value::Null,
+            VTYPE_BOOL => self.get_bool().unwrap_or_default().into(),
+            VTYPE_INT => self.get_int().unwrap_or_default().into(),
+            VTYPE_DOUBLE => self.get_double().unwrap_or_default().into(),
+            VTYPE_STRING => self.get_string().unwrap_or_default().to_string().into(),
+            VTYPE_BINARY => {
+                let binary = self.get_binary().ok_or(Error::NullPtr)?;
+                let bytes = binary.get_inner_data();
+                wide_integer(bytes).unwrap_or_else(|| bytes.to_vec().into())
+            }
+            VTYPE_LIST => {
+                let list = self.get_list().ok_or(Error::NullPtr)?;
+                let items = (0..list.get_size().unwrap_or_default())
+                    .map(|index| list.get_value(index).ok_or(Error::NullPtr)?.to_json())
+                    .collect::<Result<Vec<_>>>()?;
+                serde_json::Value::Array(items)
+            }
+            VTYPE_DICTIONARY => {
+                let dictionary = self.get_dictionary().ok_or(Error::NullPtr)?;
+                let entries = dictionary
+                    .get_keys()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|key| {
+                        let key = key.to_string();
+                        let entry = dictionary.get_value(&key).ok_or(Error::NullPtr)?;
+                        Ok((key, entry.to_json()?))
+                    })
+                    .collect::<Result<serde_json::Map<_, _>>>()?;
+                serde_json::Value::Object(entries)
+            }
+            _ => return Err(Error::NullPtr),
+        };
+        Ok(json)
+    }
+}
+
+/// The integer held by a binary value created by [CefValue::from_json], if `bytes` are one.
+fn wide_integer(bytes: &[u8]) -> Option<serde_json::Value> {
+    if let Some(integer) = bytes.strip_prefix(I64_TAG) {
+        return Some(i64::from_le_bytes(integer.try_into().ok()?).into());
+    }
+    let integer = bytes.strip_prefix(U64_TAG)?;
+    Some(u64::from_le_bytes(integer.try_into().ok()?).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(json: serde_json::Value) -> serde_json::Value {
+        CefValue::from_json(&json).unwrap().to_json().unwrap()
+    }
+
+    #[test]
+    fn integers_round_trip() {
+        let integers = serde_json::json!([i64::MAX, i64::MIN, u64::MAX, -1, 3_000_000_000u64]);
+        assert_eq!(round_trip(integers.clone()), integers);
+
+        let json = round_trip(serde_json::json!({ "size": u64::MAX, "offset": i64::MAX }));
+        assert_eq!(
+            serde_json::from_value::<u64>(json["size"].clone()).unwrap(),
+            u64::MAX
+        );
+        assert_eq!(
+            serde_json::from_value::<i64>(json["offset"].clone()).unwrap(),
+            i64::MAX
+        );
+        assert_eq!(
+            serde_json::from_value::<i32>(round_trip((-1).into())).unwrap(),
+            -1
+        );
+    }
+
+    #[test]
+    fn objects_stay_objects() {
+        let objects = serde_json::json!([
+            { "$cef::Integer": "5" },
+            { "$cef::i64": 5 },
+            { "n": i64::MAX, "s": "$cef::i64" },
+        ]);
+        assert_eq!(round_trip(objects.clone()), objects);
+    }
+
+    #[test]
+    fn doubles_stay_doubles() {
+        assert_eq!(round_trip(1.5.into()), serde_json::json!(1.5));
+        assert_eq!(round_trip(3e9.into()), serde_json::json!(3e9));
+    }
+}
@@ -6,6 +6,7 @@ use crate::prelude::*;
 pub struct CefDictionaryValue(cef_dictionary_value_t);
 
 impl CefDictionaryValue {
+    /// See [cef_dictionary_value_create]
     pub fn create() -> Result<CefDictionaryValue> {
         let ptr = unsafe { cef_dictionary_value_create() };
         if ptr.is_null() {
@@ -14,4 +15,218 @@ impl CefDictionaryValue {
             Ok(CefDictionaryValue::from(ptr))
         }
     }
+
+    wrapper_methods!(
+        /// See [cef_dictionary_value_t::is_valid]
+        fn is_valid(&self) -> bool;
+
+        /// See [cef_dictionary_value_t::is_owned]
+        fn is_owned(&self) -> bool;
+
+        /// See [cef_dictionary_value_t::is_read_only]
+        fn is_read_only(&self) -> bool;
+
+        /// See [cef_dictionary_value_t::is_same]
+        fn is_same(&self, that: CefDictionaryValue) -> bool {
+            is_same.map(|f| unsafe { f(self.get_this(), that.into_raw()) == 1 })
+        }
+
+        /// See [cef_dictionary_value_t::is_equal]
+        fn is_equal(&self, that: CefDictionaryValue) -> bool {
+            is_equal.map(|f| unsafe { f(self.get_this(), that.into_raw()) == 1 })
+        }
+
+        /// See [cef_dictionary_value_t::copy]
+        fn copy(&self, exclude_empty_children: bool) -> CefDictionaryValue {
+            copy.and_then(|f| unsafe {
+                let v = f(self.get_this(), exclude_empty_children as _);
+                if v.is_null() {
+                    None
+                } else {
+                    Some(CefDictionaryValue::from(v))
+                }
+            })
+        }
+
+        /// See [cef_dictionary_value_t::get_size]
+        fn get_size(&self) -> usize;
+
+        /// See [cef_dictionary_value_t::clear]
+        fn clear(&self) -> bool;
+
+        /// See [cef_dictionary_value_t::has_key]
+        fn has_key(&self, key: &str) -> bool {
+            has_key.map(|f| unsafe { f(self.get_this(), &CefString::from(key).as_raw()) == 1 })
+        }
+
+        /// See [cef_dictionary_value_t::get_keys]
+        fn get_keys(&self) -> Vec<CefString> {
+            get_keys.and_then(|f| unsafe {
+                let list = cef_string_list_alloc();
+                let ok = f(self.get_this(), list) == 1;
+                let keys = crate::string::parse_string_list(list);
+                cef_string_list_free(list);
+                ok.then_some(keys)
+            })
+        }
+
+        /// See [cef_dictionary_value_t::remove]
+        fn remove(&self, key: &str) -> bool {
+            remove.map(|f| unsafe { f(self.get_this(), &CefString::from(key).as_raw()) == 1 })
+        }
+
+        /// See [cef_dictionary_value_t::get_type]
+        fn get_type(&self, key: &str) -> crate::CefValueType {
+            get_type.map(|f| unsafe { f(self.get_this(), &CefString::from(key).as_raw()) })
+        }
+
+        /// See [cef_dictionary_value_t::get_value]
+        fn get_value(&self, key: &str) -> crate::CefValue {
+            get_value.and_then(|f| unsafe {
+                let v = f(self.get_this(), &CefString::from(key).as_raw());
+                if v.is_null() {
+                    None
+                } else {
+                    Some(crate::CefValue::from(v))
+                }
+            })
+        }
+
+        /// See [cef_dictionary_value_t::get_bool]
+        fn get_bool(&self, key: &str) -> bool {
+            get_bool.map(|f| unsafe { f(self.get_this(), &CefString::from(key).as_raw()) == 1 })
+        }
+
+        /// See [cef_dictionary_value_t::get_int]
+        fn get_int(&self, key: &str) -> i32 {
+            get_int.map(|f| unsafe { f(self.get_this(), &CefString::from(key).as_raw()) })
+        }
+
+        /// See [cef_dictionary_value_t::get_double]
+        fn get_double(&self, key: &str) -> f64 {
+            get_double.map(|f| unsafe { f(self.get_this(), &CefString::from(key).as_raw()) })
+        }
+
+        /// See [cef_dictionary_value_t::get_string]
+        fn get_string(&self, key: &str) -> CefString {
+            get_string.and_then(|f| unsafe {
+                CefString::from_userfree_cef(f(self.get_this(), &CefString::from(key).as_raw()))
+            })
+        }
+
+        /// See [cef_dictionary_value_t::get_binary]
+        fn get_binary(&self, key: &str) -> crate::CefBinaryValue {
+            get_binary.and_then(|f| unsafe {
+                let v = f(self.get_this(), &CefString::from(key).as_raw());
+                if v.is_null() {
+                    None
+                } else {
+                    Some(crate::CefBinaryValue::from(v))
+                }
+            })
+        }
+
+        /// See [cef_dictionary_value_t::get_dictionary]
+        fn get_dictionary(&self, key: &str) -> CefDictionaryValue {
+            get_dictionary.and_then(|f| unsafe {
+                let v = f(self.get_this(), &CefString::from(key).as_raw());
+                if v.is_null() {
+                    None
+                } else {
+                    Some(CefDictionaryValue::from(v))
+                }
+            })
+        }
+
+        /// See [cef_dictionary_value_t::get_list]
+        fn get_list(&self, key: &str) -> crate::CefListValue {
+            get_list.and_then(|f| unsafe {
+                let v = f(self.get_this(), &CefString::from(key).as_raw());
+                if v.is_null() {
+                    None
+                } else {
+                    Some(crate::CefListValue::from(v))
+                }
+            })
+        }
+
+        /// See [cef_dictionary_value_t::set_value]
+        fn set_value(&self, key: &str, value: crate::CefValue) -> bool {
+            set_value.map(|f| unsafe {
+                f(
+                    self.get_this(),
+                    &CefString::from(key).as_raw(),
+                    value.into_raw(),
+                ) == 1
+            })
+        }
+
+        /// See [cef_dictionary_value_t::set_null]
+        fn set_null(&self, key: &str) -> bool {
+            set_null.map(|f| unsafe { f(self.get_this(), &CefString::from(key).as_raw()) == 1 })
+        }
+
+        /// See [cef_dictionary_value_t::set_bool]
+        fn set_bool(&self, key: &str, value: bool) -> bool {
+            set_bool.map(|f| unsafe {
+                f(self.get_this(), &CefString::from(key).as_raw(), value as _) == 1
+            })
+        }
+
+        /// See [cef_dictionary_value_t::set_int]
+        fn set_int(&self, key: &str, value: i32) -> bool {
+            set_int
+                .map(|f| unsafe { f(self.get_this(), &CefString::from(key).as_raw(), value) == 1 })
+        }
+
+        /// See [cef_dictionary_value_t::set_double]
+        fn set_double(&self, key: &str, value: f64) -> bool {
+            set_double
+                .map(|f| unsafe { f(self.get_this(), &CefString::from(key).as_raw(), value) == 1 })
+        }
+
+        /// See [cef_dictionary_value_t::set_string]
+        fn set_string(&self, key: &str, value: CefString) -> bool {
+            set_string.map(|f| unsafe {
+                f(
+                    self.get_this(),
+                    &CefString::from(key).as_raw(),
+                    &value.as_raw(),
+                ) == 1
+            })
+        }
+
+        /// See [cef_dictionary_value_t::set_binary]
+        fn set_binary(&self, key: &str, value: crate::CefBinaryValue) -> bool {
+            set_binary.map(|f| unsafe {
+                f(
+                    self.get_this(),
+                    &CefString::from(key).as_raw(),
+                    value.into_raw(),
+                ) == 1
+            })
+        }
+
+        /// See [cef_dictionary_value_t::set_dictionary]
+        fn set_dictionary(&self, key: &str, value: CefDictionaryValue) -> bool {
+            set_dictionary.map(|f| unsafe {
+                f(
+                    self.get_this(),
+                    &CefString::from(key).as_raw(),
+                    value.into_raw(),
+                ) == 1
+            })
+        }
+
+        /// See [cef_dictionary_value_t::set_list]
+        fn set_list(&self, key: &str, value: crate::CefListValue) -> bool {
+            set_list.map(|f| unsafe {
+                f(
+                    self.get_this(),
+                    &CefString::from(key).as_raw(),
+                    value.into_raw(),
+                ) == 1
+            })
+        }
+    );
 }
@@ -1,11 +1,14 @@
 use crate::prelude::*;
 use crate::{
+    add_view_delegate_methods,
     client::CefClient,
     error::{Error, Result},
+    rc::RcImpl,
     string::CefString,
+    view::ViewDelegate,
     CefBrowserSettings,
 };
-use cef_sys::{cef_browser_view_create, cef_browser_view_t};
+use cef_sys::{cef_browser_view_create, cef_browser_view_delegate_t, cef_browser_view_t};
 use std::ptr::null_mut;
 
 /// See [cef_browser_view_t] for more documentation.
@@ -40,6 +43,36 @@ impl CefBrowserView {
         Ok(CefBrowserView::from(view))
     }
 
+    /// See [cef_browser_view_create] for more documentation.
+    ///
+    /// Unlike [CefBrowserView::create], the `delegate` will be notified about popup browser
+    /// views, including DevTools. See [BrowserViewDelegate::on_popup_browser_view_created].
+    pub fn create_with_delegate<T: CefClient, D: BrowserViewDelegate>(
+        client: Option<T>,
+        url: &CefString,
+        settings: CefBrowserSettings,
+        delegate: D,
+    ) -> Result<CefBrowserView> {
+        let client = client.map(|c| c.into_raw()).unwrap_or(null_mut());
+        let request_context = crate::net::CefRequestContext::global();
+
+        let view = unsafe {
+            cef_browser_view_create(
+                client,
+                &url.as_raw(),
+                &settings.as_raw(),
+                null_mut(),
+                request_context.into_raw(),
+                BrowserViewDelegate::into_raw(delegate),
+            )
+        };
+        if view.is_null() {
+            return Err(Error::CannotCreateBrowserView);
+        }
+
+        Ok(CefBrowserView::from(view))
+    }
+
     wrapper_methods! {
         /// See [cef_browser_view_t::get_browser] for more documentation.
         fn get_browser(&self) -> crate::browser::CefBrowser {
@@ -55,7 +88,8 @@ impl CefBrowserView {
     }
 }
 
-pub trait BrowserViewDelegate: Sized {
+/// See [cef_browser_view_delegate_t] for more documentation.
+pub trait BrowserViewDelegate: ViewDelegate {
     /// See [cef_sys::cef_browser_view_delegate_t::on_browser_created]
     fn on_browser_created(&self, _browser_view: CefBrowserView, _browser: crate::CefBrowser) {}
 
@@ -76,19 +110,23 @@ pub trait BrowserViewDelegate: Sized {
         &self,
         _browser_view: CefBrowserView,
         _settings: CefBrowserSettings,
-        _client: cef_client_t,
+        _client: crate::CefAnyClient,
         _is_devtools: bool,
     ) -> Option<Self> {
         None
     }
 
     /// See [cef_sys::cef_browser_view_delegate_t::on_popup_browser_view_created]
+    ///
+    /// When `is_devtools` is true, `popup_browser_view` hosts DevTools. Add it to a panel of the
+    /// existing window and return `true` to dock it instead of opening a new window.
     fn on_popup_browser_view_created(
         &self,
         _browser_view: CefBrowserView,
         _popup_browser_view: CefBrowserView,
         _is_devtools: bool,
-    ) {
+    ) -> bool {
+        false
     }
 
     /// See [cef_sys::cef_browser_view_delegate_t::get_chrome_toolbar_type]
@@ -108,4 +146,72 @@ pub trait BrowserViewDelegate: Sized {
     fn get_browser_runtime_style(&self) -> crate::CefRuntimeStyle {
         todo!()
     }
+
+    fn into_raw(self) -> *mut cef_browser_view_delegate_t {
+        let mut object: cef_browser_view_delegate_t = unsafe { std::mem::zeroed() };
+
+        let view = &mut object.base;
+        add_view_delegate_methods!(view);
+
+        object.on_browser_created = Some(on_browser_created::<Self>);
+        object.on_browser_destroyed = Some(on_browser_destroyed::<Self>);
+        object.get_delegate_for_popup_browser_view =
+            Some(get_delegate_for_popup_browser_view::<Self>);
+        object.on_popup_browser_view_created = Some(on_popup_browser_view_created::<Self>);
+
+        RcImpl::new(object, self).cast()
+    }
+}
+
+extern "C" fn on_browser_created<I: BrowserViewDelegate>(
+    this: *mut cef_browser_view_delegate_t,
+    browser_view: *mut cef_browser_view_t,
+    browser: *mut cef_browser_t,
+) {
+    let obj: &RcImpl<_, I> = RcImpl::get(this);
+    let browser_view = CefBrowserView::from(browser_view);
+    let browser = crate::CefBrowser::from(browser);
+    obj.interface.on_browser_created(browser_view, browser);
+}
+
+extern "C" fn on_browser_destroyed<I: BrowserViewDelegate>(
+    this: *mut cef_browser_view_delegate_t,
+    browser_view: *mut cef_browser_view_t,
+    browser: *mut cef_browser_t,
+) {
+    let obj: &RcImpl<_, I> = RcImpl::get(this);
+    let browser_view = CefBrowserView::from(browser_view);
+    let browser = crate::CefBrowser::from(browser);
+    obj.interface.on_browser_destroyed(browser_view, browser);
+}
+
+extern "C" fn get_delegate_for_popup_browser_view<I: BrowserViewDelegate>(
+    this: *mut cef_browser_view_delegate_t,
+    browser_view: *mut cef_browser_view_t,
+    settings: *const cef_browser_settings_t,
+    client: *mut cef_client_t,
+    is_devtools: i32,
+) -> *mut cef_browser_view_delegate_t {
+    let obj: &RcImpl<_, I> = RcImpl::get(this);
+    let browser_view = CefBrowserView::from(browser_view);
+    let settings = unsafe { CefBrowserSettings::from_raw(*settings) };
+    let client = crate::CefAnyClient::from(client);
+    obj.interface
+        .get_delegate_for_popup_browser_view(browser_view, settings, client, is_devtools != 0)
+        .map(BrowserViewDelegate::into_raw)
+        .unwrap_or(null_mut())
+}
+
+extern "C" fn on_popup_browser_view_created<I: BrowserViewDelegate>(
+    this: *mut cef_browser_view_delegate_t,
+    browser_view: *mut cef_browser_view_t,
+    popup_browser_view: *mut cef_browser_view_t,
+    is_devtools: i32,
+) -> i32 {
+    let obj: &RcImpl<_, I> = RcImpl::get(this);
+    let browser_view = CefBrowserView::from(browser_view);
+    let popup_browser_view = CefBrowserView::from(popup_browser_view);
+    obj.interface
+        .on_popup_browser_view_created(browser_view, popup_browser_view, is_devtools != 0)
+        as i32
 }
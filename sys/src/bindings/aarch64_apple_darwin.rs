@@ -5771,6 +5771,61 @@ extern "C" {
     #[doc = "\n Create a new cef_process_message_t object with the specified name.\n"]
     pub fn cef_process_message_create(name: *const cef_string_t) -> *mut cef_process_message_t;
 }
+#[doc = "\n Structure that builds a cef_process_message_t containing a shared memory\n region. This structure is not thread-safe but may be used exclusively on a\n different thread from the one which constructed it.\n"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, crate :: FfiRc)]
+pub struct _cef_shared_process_message_builder_t {
+    #[doc = "\n Base structure.\n"]
+    pub base: cef_base_ref_counted_t,
+    #[doc = "\n Returns true (1) if the builder is valid.\n"]
+    pub is_valid: ::std::option::Option<
+        unsafe extern "C" fn(
+            self_: *mut _cef_shared_process_message_builder_t,
+        ) -> ::std::os::raw::c_int,
+    >,
+    #[doc = "\n Returns the size of the shared memory region in bytes. Returns 0 for\n invalid instances.\n"]
+    pub size: ::std::option::Option<
+        unsafe extern "C" fn(self_: *mut _cef_shared_process_message_builder_t) -> usize,
+    >,
+    #[doc = "\n Returns the pointer to the writable memory. Returns nullptr for invalid\n instances. The returned pointer is only valid for the life span of this\n object.\n"]
+    pub memory: ::std::option::Option<
+        unsafe extern "C" fn(
+            self_: *mut _cef_shared_process_message_builder_t,
+        ) -> *mut ::std::os::raw::c_void,
+    >,
+    #[doc = "\n Creates a new cef_process_message_t from the data provided to the builder.\n Returns nullptr for invalid instances. Invalidates the builder instance.\n"]
+    pub build: ::std::option::Option<
+        unsafe extern "C" fn(
+            self_: *mut _cef_shared_process_message_builder_t,
+        ) -> *mut _cef_process_message_t,
+    >,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of _cef_shared_process_message_builder_t"]
+        [::std::mem::size_of::<_cef_shared_process_message_builder_t>() - 72usize];
+    ["Alignment of _cef_shared_process_message_builder_t"]
+        [::std::mem::align_of::<_cef_shared_process_message_builder_t>() - 8usize];
+    ["Offset of field: _cef_shared_process_message_builder_t::base"]
+        [::std::mem::offset_of!(_cef_shared_process_message_builder_t, base) - 0usize];
+    ["Offset of field: _cef_shared_process_message_builder_t::is_valid"]
+        [::std::mem::offset_of!(_cef_shared_process_message_builder_t, is_valid) - 40usize];
+    ["Offset of field: _cef_shared_process_message_builder_t::size"]
+        [::std::mem::offset_of!(_cef_shared_process_message_builder_t, size) - 48usize];
+    ["Offset of field: _cef_shared_process_message_builder_t::memory"]
+        [::std::mem::offset_of!(_cef_shared_process_message_builder_t, memory) - 56usize];
+    ["Offset of field: _cef_shared_process_message_builder_t::build"]
+        [::std::mem::offset_of!(_cef_shared_process_message_builder_t, build) - 64usize];
+};
+#[doc = "\n Structure that builds a cef_process_message_t containing a shared memory\n region. This structure is not thread-safe but may be used exclusively on a\n different thread from the one which constructed it.\n"]
+pub type cef_shared_process_message_builder_t = _cef_shared_process_message_builder_t;
+extern "C" {
+    #[doc = "\n Creates a new cef_shared_process_message_builder_t that will internally\n create a shared memory region of |byte_size| bytes and a process message\n named |name|.\n"]
+    pub fn cef_shared_process_message_builder_create(
+        name: *const cef_string_t,
+        byte_size: usize,
+    ) -> *mut cef_shared_process_message_builder_t;
+}
 #[doc = "\n Structure used to represent a web request. The functions of this structure\n may be called on any thread.\n"]
 #[repr(C)]
 #[derive(Debug, Copy, Clone, crate :: FfiRc)]
@@ -6108,16 +6163,141 @@ const _: () = {
 };
 #[doc = "\n Implement this structure to receive string values asynchronously.\n"]
 pub type cef_string_visitor_t = _cef_string_visitor_t;
+#[doc = "\n Structure used to make a URL request. URL requests are not associated with a\n browser instance so no cef_client_t callbacks will be executed. URL requests\n can be created on any valid CEF thread in either the browser or render\n process. Once created the functions of the URL request object must be\n accessed on the same thread that created it.\n"]
 #[repr(C)]
 #[derive(Debug, Copy, Clone, crate :: FfiRc)]
-pub struct _cef_urlrequest_client_t {
-    _unused: [u8; 0],
+pub struct _cef_urlrequest_t {
+    #[doc = "\n Base structure.\n"]
+    pub base: cef_base_ref_counted_t,
+    #[doc = "\n Returns the request object used to create this URL request. The returned\n object is read-only and should not be modified.\n"]
+    pub get_request: ::std::option::Option<
+        unsafe extern "C" fn(self_: *mut _cef_urlrequest_t) -> *mut _cef_request_t,
+    >,
+    #[doc = "\n Returns the client.\n"]
+    pub get_client: ::std::option::Option<
+        unsafe extern "C" fn(self_: *mut _cef_urlrequest_t) -> *mut _cef_urlrequest_client_t,
+    >,
+    #[doc = "\n Returns the request status.\n"]
+    pub get_request_status: ::std::option::Option<
+        unsafe extern "C" fn(self_: *mut _cef_urlrequest_t) -> cef_urlrequest_status_t,
+    >,
+    #[doc = "\n Returns the request error if status is UR_CANCELED or UR_FAILED, or 0\n otherwise.\n"]
+    pub get_request_error: ::std::option::Option<
+        unsafe extern "C" fn(self_: *mut _cef_urlrequest_t) -> cef_errorcode_t,
+    >,
+    #[doc = "\n Returns the response, or NULL if no response information is available.\n Response information will only be available after the upload has\n completed. The returned object is read-only and should not be modified.\n"]
+    pub get_response: ::std::option::Option<
+        unsafe extern "C" fn(self_: *mut _cef_urlrequest_t) -> *mut _cef_response_t,
+    >,
+    #[doc = "\n Returns true (1) if the response body was served from the cache. This\n includes responses for which revalidation was required.\n"]
+    pub response_was_cached: ::std::option::Option<
+        unsafe extern "C" fn(self_: *mut _cef_urlrequest_t) -> ::std::os::raw::c_int,
+    >,
+    #[doc = "\n Cancel the request.\n"]
+    pub cancel: ::std::option::Option<unsafe extern "C" fn(self_: *mut _cef_urlrequest_t)>,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of _cef_urlrequest_t"][::std::mem::size_of::<_cef_urlrequest_t>() - 96usize];
+    ["Alignment of _cef_urlrequest_t"][::std::mem::align_of::<_cef_urlrequest_t>() - 8usize];
+    ["Offset of field: _cef_urlrequest_t::base"]
+        [::std::mem::offset_of!(_cef_urlrequest_t, base) - 0usize];
+    ["Offset of field: _cef_urlrequest_t::get_request"]
+        [::std::mem::offset_of!(_cef_urlrequest_t, get_request) - 40usize];
+    ["Offset of field: _cef_urlrequest_t::get_client"]
+        [::std::mem::offset_of!(_cef_urlrequest_t, get_client) - 48usize];
+    ["Offset of field: _cef_urlrequest_t::get_request_status"]
+        [::std::mem::offset_of!(_cef_urlrequest_t, get_request_status) - 56usize];
+    ["Offset of field: _cef_urlrequest_t::get_request_error"]
+        [::std::mem::offset_of!(_cef_urlrequest_t, get_request_error) - 64usize];
+    ["Offset of field: _cef_urlrequest_t::get_response"]
+        [::std::mem::offset_of!(_cef_urlrequest_t, get_response) - 72usize];
+    ["Offset of field: _cef_urlrequest_t::response_was_cached"]
+        [::std::mem::offset_of!(_cef_urlrequest_t, response_was_cached) - 80usize];
+    ["Offset of field: _cef_urlrequest_t::cancel"]
+        [::std::mem::offset_of!(_cef_urlrequest_t, cancel) - 88usize];
+};
+#[doc = "\n Structure used to make a URL request. URL requests are not associated with a\n browser instance so no cef_client_t callbacks will be executed. URL requests\n can be created on any valid CEF thread in either the browser or render\n process. Once created the functions of the URL request object must be\n accessed on the same thread that created it.\n"]
+pub type cef_urlrequest_t = _cef_urlrequest_t;
+extern "C" {
+    #[doc = "\n Create a new URL request that is not associated with a specific browser or\n frame. Use cef_frame_t::CreateURLRequest instead if you want the request to\n have this association, in which case it may be handled differently (see\n documentation on that function). A request created with this function may\n only originate from the browser process, and will behave as follows:\n   - It may be intercepted by the client via CefResourceRequestHandler or\n     CefSchemeHandlerFactory.\n   - POST data may only contain only a single element of type PDE_TYPE_FILE\n     or PDE_TYPE_BYTES.\n   - If |request_context| is empty the global request context will be used.\n\n The |request| object will be marked as read-only after calling this\n function.\n"]
+    pub fn cef_urlrequest_create(
+        request: *mut _cef_request_t,
+        client: *mut _cef_urlrequest_client_t,
+        request_context: *mut _cef_request_context_t,
+    ) -> *mut cef_urlrequest_t;
 }
+#[doc = "\n Structure that should be implemented by the cef_urlrequest_t client. The\n functions of this structure will be called on the same thread that created\n the request unless otherwise documented.\n"]
 #[repr(C)]
 #[derive(Debug, Copy, Clone, crate :: FfiRc)]
-pub struct _cef_urlrequest_t {
-    _unused: [u8; 0],
+pub struct _cef_urlrequest_client_t {
+    #[doc = "\n Base structure.\n"]
+    pub base: cef_base_ref_counted_t,
+    #[doc = "\n Notifies the client that the request has completed. Use the\n cef_urlrequest_t::GetRequestStatus function to determine if the request\n was successful or not.\n"]
+    pub on_request_complete: ::std::option::Option<
+        unsafe extern "C" fn(self_: *mut _cef_urlrequest_client_t, request: *mut _cef_urlrequest_t),
+    >,
+    #[doc = "\n Notifies the client of upload progress. |current| denotes the number of\n bytes sent so far and |total| is the total size of uploading data (or -1\n if chunked upload is enabled). This function will only be called if the\n UR_FLAG_REPORT_UPLOAD_PROGRESS flag is set on the request.\n"]
+    pub on_upload_progress: ::std::option::Option<
+        unsafe extern "C" fn(
+            self_: *mut _cef_urlrequest_client_t,
+            request: *mut _cef_urlrequest_t,
+            current: i64,
+            total: i64,
+        ),
+    >,
+    #[doc = "\n Notifies the client of download progress. |current| denotes the number of\n bytes received up to the call and |total| is the expected total size of\n the response (or -1 if not determined).\n"]
+    pub on_download_progress: ::std::option::Option<
+        unsafe extern "C" fn(
+            self_: *mut _cef_urlrequest_client_t,
+            request: *mut _cef_urlrequest_t,
+            current: i64,
+            total: i64,
+        ),
+    >,
+    #[doc = "\n Called when some part of the response is read. |data| contains the current\n bytes received since the last call. This function will not be called if\n the UR_FLAG_NO_DOWNLOAD_DATA flag is set on the request.\n"]
+    pub on_download_data: ::std::option::Option<
+        unsafe extern "C" fn(
+            self_: *mut _cef_urlrequest_client_t,
+            request: *mut _cef_urlrequest_t,
+            data: *const ::std::os::raw::c_void,
+            data_length: usize,
+        ),
+    >,
+    #[doc = "\n Called on the IO thread when the browser needs credentials from the user.\n |isProxy| indicates whether the host is a proxy server. |host| contains the\n hostname and |port| contains the port number. Return true (1) to continue\n the request and call cef_auth_callback_t::cont() when the authentication\n information is available. If the request has an associated browser/frame\n then returning false (0) will result in a call to GetAuthCredentials on\n the cef_request_handler_t associated with that browser, if any. Otherwise,\n returning false (0) will cancel the request immediately. This function\n will only be called for requests initiated from the browser process.\n"]
+    pub get_auth_credentials: ::std::option::Option<
+        unsafe extern "C" fn(
+            self_: *mut _cef_urlrequest_client_t,
+            isProxy: ::std::os::raw::c_int,
+            host: *const cef_string_t,
+            port: ::std::os::raw::c_int,
+            realm: *const cef_string_t,
+            scheme: *const cef_string_t,
+            callback: *mut _cef_auth_callback_t,
+        ) -> ::std::os::raw::c_int,
+    >,
 }
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of _cef_urlrequest_client_t"]
+        [::std::mem::size_of::<_cef_urlrequest_client_t>() - 80usize];
+    ["Alignment of _cef_urlrequest_client_t"]
+        [::std::mem::align_of::<_cef_urlrequest_client_t>() - 8usize];
+    ["Offset of field: _cef_urlrequest_client_t::base"]
+        [::std::mem::offset_of!(_cef_urlrequest_client_t, base) - 0usize];
+    ["Offset of field: _cef_urlrequest_client_t::on_request_complete"]
+        [::std::mem::offset_of!(_cef_urlrequest_client_t, on_request_complete) - 40usize];
+    ["Offset of field: _cef_urlrequest_client_t::on_upload_progress"]
+        [::std::mem::offset_of!(_cef_urlrequest_client_t, on_upload_progress) - 48usize];
+    ["Offset of field: _cef_urlrequest_client_t::on_download_progress"]
+        [::std::mem::offset_of!(_cef_urlrequest_client_t, on_download_progress) - 56usize];
+    ["Offset of field: _cef_urlrequest_client_t::on_download_data"]
+        [::std::mem::offset_of!(_cef_urlrequest_client_t, on_download_data) - 64usize];
+    ["Offset of field: _cef_urlrequest_client_t::get_auth_credentials"]
+        [::std::mem::offset_of!(_cef_urlrequest_client_t, get_auth_credentials) - 72usize];
+};
+#[doc = "\n Structure that should be implemented by the cef_urlrequest_t client. The\n functions of this structure will be called on the same thread that created\n the request unless otherwise documented.\n"]
+pub type cef_urlrequest_client_t = _cef_urlrequest_client_t;
 #[doc = "\n Structure used to represent a frame in the browser window. When used in the\n browser process the functions of this structure may be called on any thread\n unless otherwise indicated in the comments. When used in the render process\n the functions of this structure may only be called on the main thread.\n"]
 #[repr(C)]
 #[derive(Debug, Copy, Clone, crate :: FfiRc)]
@@ -21,6 +21,10 @@
 #include "include/capi/cef_command_line_capi.h"
 #include "include/capi/cef_command_handler_capi.h"
 
+#include "include/capi/cef_dom_capi.h"
+
+#include "include/capi/cef_shared_process_message_builder_capi.h"
+
 #include "include/capi/cef_request_capi.h"
 #include "include/capi/cef_request_context_handler_capi.h"
 #include "include/capi/cef_request_handler_capi.h"
@@ -29,6 +33,7 @@
 #include "include/capi/cef_resource_bundle_handler_capi.h"
 #include "include/capi/cef_response_capi.h"
 #include "include/capi/cef_response_filter_capi.h"
+#include "include/capi/cef_urlrequest_capi.h"
 
 #include "include/capi/cef_resource_handler_capi.h"
 #include "include/capi/cef_resource_request_handler_capi.h"